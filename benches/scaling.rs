@@ -0,0 +1,67 @@
+//! Times `Day5::parse` and `Day7::parse_reader` on generated inputs of
+//! doubling size, printing the best of a few runs for each size along with
+//! the time per line. If parsing is linear in the input, the total doubles
+//! with each size and the time per line stays roughly level; time per line
+//! that keeps climbing as the sizes double points at something quadratic.
+//!
+//! Run with `cargo bench --bench scaling`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoc_2023::days::day5::Day5;
+use aoc_2023::days::day7::Day7;
+use aoc_2023::solution::Solution;
+
+const SIZES: [usize; 7] = [10_000, 20_000, 40_000, 80_000, 160_000, 320_000, 640_000];
+const RUNS: usize = 5;
+
+fn day5(lines: usize) -> String {
+    let rows: String = (0..lines)
+        .map(|i| format!("{} {} {}\n", i * 7 % 1000, i * 13 % 1000, i % 50 + 1))
+        .collect();
+    format!("seeds: 79 14 55 13\n\nseed-to-soil map:\n{rows}")
+}
+
+fn day7(lines: usize) -> String {
+    const LABELS: [char; 13] = [
+        'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
+    ];
+    (0..lines)
+        .map(|i| {
+            let hand: String = (0..5).map(|j| LABELS[(i * 5 + j * 3) % 13]).collect();
+            format!("{hand} {}\n", i % 1000)
+        })
+        .collect()
+}
+
+fn time(input: &str, parse: fn(&str)) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            parse(black_box(input));
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn bench(name: &str, generate: fn(usize) -> String, parse: fn(&str)) {
+    println!("{name}");
+    println!("{:>8} {:>12} {:>12}", "lines", "total", "per line");
+    for lines in SIZES {
+        let elapsed = time(&generate(lines), parse);
+        let per_line = elapsed / lines as u32;
+        println!("{lines:>8} {elapsed:>12.2?} {per_line:>12.2?}");
+    }
+    println!();
+}
+
+fn main() {
+    bench("day5", day5, |input| {
+        black_box(&Day5::parse(input).unwrap());
+    });
+    bench("day7", day7, |input| {
+        black_box(&Day7::parse_reader(input.as_bytes()).unwrap());
+    });
+}
@@ -0,0 +1,273 @@
+use std::cmp::Reverse;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Expr, Fields, Ident, Lifetime, LitStr};
+
+/// Derives `lextools::Lexer` for a token enum from attributes on its variants:
+///
+/// - `#[token(":")]` on a unit variant matches that text. Alphabetic text is a
+///   keyword, and only matches a whole identifier.
+/// - `#[token("red", Color::Red)]` on a one-field variant matches the keyword
+///   and stores the given value in the field.
+/// - `#[regex_number]` on one one-field variant reads a number into the
+///   field's integer type. `#[regex_number(signed)]` also takes a leading `-` or `+`,
+///   ahead of any `"-"` or `"+"` token.
+/// - `#[ident]` on one one-field variant takes any identifier that isn't a
+///   keyword. Without it, unknown identifiers are an error.
+///
+/// Spaces between tokens are skipped, and anything left unmatched is an
+/// unknown character.
+#[proc_macro_derive(Lexer, attributes(token, regex_number, ident))]
+pub fn derive_lexer(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The arguments of `#[token(...)]`: the text, and the field value if any.
+struct TokenArgs {
+    text: LitStr,
+    value: Option<Expr>,
+}
+
+impl Parse for TokenArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let text = input.parse()?;
+        let value = match input.parse::<Option<syn::Token![,]>>()? {
+            Some(_) => Some(input.parse()?),
+            None => None,
+        };
+        Ok(TokenArgs { text, value })
+    }
+}
+
+/// The argument of `#[regex_number(signed)]`.
+struct Signed;
+
+impl Parse for Signed {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        if ident != "signed" {
+            return Err(Error::new_spanned(ident, "expected `signed`"));
+        }
+        Ok(Signed)
+    }
+}
+
+/// What a lexer needs to know about a token enum.
+#[derive(Default)]
+struct Vocabulary {
+    literals: Vec<(String, TokenStream2)>,
+    keywords: Vec<(String, TokenStream2)>,
+    number: Option<(Ident, bool)>,
+    ident: Option<Ident>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "Lexer can only be derived for enums",
+        ));
+    };
+    if let Some(param) = input.generics.type_params().next() {
+        return Err(Error::new_spanned(
+            param,
+            "Lexer can't be derived for enums with type parameters",
+        ));
+    }
+
+    let vocabulary = data
+        .variants
+        .iter()
+        .try_fold(Vocabulary::default(), |vocabulary, variant| {
+            collect(vocabulary, variant)
+        })?;
+
+    let name = &input.ident;
+    let (enum_generics, ty_generics, _) = input.generics.split_for_impl();
+    let (impl_generics, lifetime, table_lifetime) = match input.generics.lifetimes().next() {
+        Some(def) => (
+            quote!(#enum_generics),
+            def.lifetime.clone(),
+            def.lifetime.clone(),
+        ),
+        None => {
+            let lifetime = Lifetime::new("'input", Span::call_site());
+            let table_lifetime = Lifetime::new("'static", Span::call_site());
+            (quote!(<#lifetime>), lifetime, table_lifetime)
+        }
+    };
+
+    let mut literals = vocabulary.literals;
+    literals.sort_by_key(|(text, _)| Reverse(text.len()));
+    let literals = literals.iter().map(|(text, token)| {
+        let len = text.len();
+        quote! {
+            if input[pos..].starts_with(#text) {
+                return Ok((pos + #len, #token));
+            }
+        }
+    });
+
+    let (signed, number) = match vocabulary.number {
+        Some((variant, true)) => (
+            Some(quote! {
+                if lt::is_signed_number(input, pos) {
+                    let (pos, number) = lt::read_signed(input, pos)?;
+                    return Ok((pos, Self::#variant(number)));
+                }
+            }),
+            None,
+        ),
+        Some((variant, false)) => (
+            None,
+            Some(quote! {
+                if c.is_ascii_digit() {
+                    let (pos, number) = lt::read_number(input, pos)?;
+                    return Ok((pos, Self::#variant(number)));
+                }
+            }),
+        ),
+        None => (None, None),
+    };
+
+    // The keyword table is a constant, so it isn't built again on every step.
+    let has_keywords = !vocabulary.keywords.is_empty() || vocabulary.ident.is_some();
+    let (texts, tokens): (Vec<_>, Vec<_>) = vocabulary.keywords.into_iter().unzip();
+    let table = has_keywords.then(|| {
+        let keyword = quote!(::aoc_2023::parsetools::lextools::Keyword<#table_lifetime, Self>);
+        quote! {
+            impl #enum_generics #name #ty_generics {
+                #[doc(hidden)]
+                const __KEYWORDS: &#table_lifetime [#keyword] = &[#((#texts, || #tokens)),*];
+            }
+        }
+    });
+    let keywords = has_keywords.then(|| {
+        let fallback = vocabulary
+            .ident
+            .map(|variant| quote!(.or_else(Self::#variant)));
+        quote! {
+            let keywords = lt::Keywords::new(Self::__KEYWORDS) #fallback;
+        }
+    });
+    let read_keyword = has_keywords.then(|| {
+        quote! {
+            if c.is_alphabetic() {
+                return lt::read_keyword(input, pos, &keywords);
+            }
+        }
+    });
+
+    Ok(quote! {
+        #table
+
+        impl #impl_generics ::aoc_2023::parsetools::lextools::Lexer<#lifetime> for #name #ty_generics {
+            fn step(
+                input: &#lifetime str,
+                pos: ::aoc_2023::parsetools::Index,
+            ) -> ::aoc_2023::parsetools::ParseResult<(::aoc_2023::parsetools::Index, Self)> {
+                use ::aoc_2023::parsetools::lextools as lt;
+
+                #keywords
+
+                let pos = lt::skip_whitespace(input, pos)?;
+                let c = lt::peek(input, pos)?;
+
+                #signed
+                #(#literals)*
+                #number
+                #read_keyword
+
+                Err(::aoc_2023::parsetools::ParseError::UnknownCharacter { pos, c })
+            }
+        }
+    })
+}
+
+/// Adds what `variant`'s attributes say about it to `vocabulary`.
+fn collect(vocabulary: Vocabulary, variant: &syn::Variant) -> syn::Result<Vocabulary> {
+    let name = &variant.ident;
+    let fields = match &variant.fields {
+        Fields::Unit => 0,
+        Fields::Unnamed(fields) => fields.unnamed.len(),
+        Fields::Named(_) => {
+            return Err(Error::new_spanned(
+                variant,
+                "Lexer tokens can't have named fields",
+            ))
+        }
+    };
+    let needs_field = |attr: &syn::Attribute| {
+        if fields == 1 {
+            Ok(())
+        } else {
+            Err(Error::new_spanned(
+                attr,
+                "expected a variant with one field",
+            ))
+        }
+    };
+
+    variant
+        .attrs
+        .iter()
+        .try_fold(vocabulary, |mut vocabulary, attr| {
+            if attr.path().is_ident("token") {
+                let TokenArgs { text, value } = attr.parse_args()?;
+                let token = match (&value, fields) {
+                    (None, 0) => quote!(Self::#name),
+                    (Some(value), 1) => quote!(Self::#name(#value)),
+                    (None, _) => {
+                        return Err(Error::new_spanned(
+                            attr,
+                            "expected a unit variant, or a value for its field",
+                        ))
+                    }
+                    (Some(_), _) => {
+                        return Err(Error::new_spanned(
+                            attr,
+                            "expected a variant with one field",
+                        ))
+                    }
+                };
+                let text = text.value();
+                if text.is_empty() {
+                    return Err(Error::new_spanned(attr, "token text can't be empty"));
+                }
+                if text.chars().all(char::is_alphabetic) {
+                    vocabulary.keywords.push((text, token));
+                } else {
+                    vocabulary.literals.push((text, token));
+                }
+            } else if attr.path().is_ident("regex_number") {
+                needs_field(attr)?;
+                let signed = match &attr.meta {
+                    syn::Meta::Path(_) => false,
+                    _ => {
+                        attr.parse_args::<Signed>()?;
+                        true
+                    }
+                };
+                if vocabulary.number.is_some() {
+                    return Err(Error::new_spanned(
+                        attr,
+                        "only one variant can be #[regex_number]",
+                    ));
+                }
+                vocabulary.number = Some((name.clone(), signed));
+            } else if attr.path().is_ident("ident") {
+                needs_field(attr)?;
+                if vocabulary.ident.is_some() {
+                    return Err(Error::new_spanned(attr, "only one variant can be #[ident]"));
+                }
+                vocabulary.ident = Some(name.clone());
+            }
+            Ok(vocabulary)
+        })
+}
@@ -0,0 +1,449 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use aoc_2023::days::{self, Answer, Day, Part, Stage, DAYS};
+use aoc_2023::functools::append;
+use aoc_2023::json::Value;
+use aoc_2023::manifest::{self, Entry, DEFAULT_MANIFEST};
+use aoc_2023::read::{normalize, Input};
+use aoc_2023::scaffold;
+use aoc_2023::solution;
+use aoc_2023::timing::{self, Summary};
+
+const USAGE: &str = "\
+usage: aoc run <day|all> [--part <1|2>] [--input <path|->] [--json]
+                          [--dump-tokens | --dump-ast]
+       aoc verify [--answers <path>]
+       aoc bench <day|all> [--iterations <n>] [--csv]
+       aoc new <day>";
+
+/// How many times `aoc bench` runs each day unless told otherwise.
+const DEFAULT_ITERATIONS: usize = 10;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {err}\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    if !run(command) {
+        process::exit(1);
+    }
+}
+
+enum Command {
+    Run {
+        days: Vec<&'static Day>,
+        options: RunOptions,
+    },
+    /// Check the days against an answers manifest.
+    Verify { manifest: PathBuf },
+    /// Time each phase of the days.
+    Bench {
+        days: Vec<&'static Day>,
+        options: BenchOptions,
+    },
+    /// Lay out a new day, ready to fill in.
+    New { number: usize },
+}
+
+struct RunOptions {
+    parts: Vec<Part>,
+    /// Where to read the input from instead of the day's usual file.
+    input: Option<Input>,
+    /// Print a JSON object per answer rather than a line of text.
+    json: bool,
+    /// Stop after this stage and show its output instead of answering.
+    dump: Option<Stage>,
+}
+
+struct BenchOptions {
+    iterations: usize,
+    /// Print CSV rather than a table, to keep results across commits.
+    csv: bool,
+}
+
+fn parse_args(args: &[&str]) -> Result<Command, String> {
+    match args {
+        ["run", day, options @ ..] => {
+            let days = parse_days(day)?;
+            let options = parse_run_options(
+                options,
+                RunOptions {
+                    parts: Part::ALL.to_vec(),
+                    input: None,
+                    json: false,
+                    dump: None,
+                },
+            )?;
+            if options.input.is_some() && days.len() > 1 {
+                return Err("--input can only be given for a single day".to_string());
+            }
+            Ok(Command::Run { days, options })
+        }
+        ["run"] => Err("missing the day to run".to_string()),
+        ["verify", options @ ..] => {
+            let manifest = parse_verify_options(options, PathBuf::from(DEFAULT_MANIFEST))?;
+            Ok(Command::Verify { manifest })
+        }
+        ["bench", day, options @ ..] => {
+            let days = parse_days(day)?;
+            let options = parse_bench_options(
+                options,
+                BenchOptions {
+                    iterations: DEFAULT_ITERATIONS,
+                    csv: false,
+                },
+            )?;
+            Ok(Command::Bench { days, options })
+        }
+        ["bench"] => Err("missing the day to bench".to_string()),
+        ["new", day] => match day.parse() {
+            Ok(number @ 1..=25) => Ok(Command::New { number }),
+            _ => Err(format!("expected a day from 1 to 25, found {day:?}")),
+        },
+        ["new"] => Err("missing the day to create".to_string()),
+        ["new", _, option, ..] => Err(format!("unknown option {option:?}")),
+        [command, ..] => Err(format!("unknown command {command:?}")),
+        [] => Err("missing a command".to_string()),
+    }
+}
+
+/// A day number, or `all` for every registered day.
+fn parse_days(day: &str) -> Result<Vec<&'static Day>, String> {
+    if day == "all" {
+        return Ok(DAYS.iter().collect());
+    }
+
+    let number = day
+        .parse()
+        .map_err(|_| format!("expected a day number or \"all\", found {day:?}"))?;
+    match days::find(number) {
+        Some(day) => Ok(vec![day]),
+        None => Err(format!("day {number} isn't solved yet")),
+    }
+}
+
+fn parse_run_options(options: &[&str], parsed: RunOptions) -> Result<RunOptions, String> {
+    match options {
+        [] => Ok(parsed),
+        ["--part", part, rest @ ..] => {
+            let part = match *part {
+                "1" => Part::One,
+                "2" => Part::Two,
+                _ => return Err(format!("expected part 1 or 2, found {part:?}")),
+            };
+            let parts = vec![part];
+            parse_run_options(rest, RunOptions { parts, ..parsed })
+        }
+        ["--input", path, rest @ ..] => {
+            let input = Some(Input::from_arg(path));
+            parse_run_options(rest, RunOptions { input, ..parsed })
+        }
+        ["--dump-tokens", rest @ ..] => {
+            let dump = Some(Stage::Tokens);
+            parse_run_options(rest, RunOptions { dump, ..parsed })
+        }
+        ["--dump-ast", rest @ ..] => {
+            let dump = Some(Stage::Ast);
+            parse_run_options(rest, RunOptions { dump, ..parsed })
+        }
+        ["--json", rest @ ..] => parse_run_options(
+            rest,
+            RunOptions {
+                json: true,
+                ..parsed
+            },
+        ),
+        [option @ ("--part" | "--input")] => Err(format!("{option} needs a value")),
+        [option, ..] => Err(format!("unknown option {option:?}")),
+    }
+}
+
+fn parse_verify_options(options: &[&str], manifest: PathBuf) -> Result<PathBuf, String> {
+    match options {
+        [] => Ok(manifest),
+        ["--answers", path, rest @ ..] => parse_verify_options(rest, PathBuf::from(path)),
+        ["--answers"] => Err("--answers needs a value".to_string()),
+        [option, ..] => Err(format!("unknown option {option:?}")),
+    }
+}
+
+fn parse_bench_options(options: &[&str], parsed: BenchOptions) -> Result<BenchOptions, String> {
+    match options {
+        [] => Ok(parsed),
+        ["--iterations", iterations, rest @ ..] => {
+            let iterations = match iterations.parse() {
+                Ok(iterations) if iterations > 0 => iterations,
+                _ => return Err(format!("expected a positive count, found {iterations:?}")),
+            };
+            parse_bench_options(
+                rest,
+                BenchOptions {
+                    iterations,
+                    ..parsed
+                },
+            )
+        }
+        ["--csv", rest @ ..] => parse_bench_options(
+            rest,
+            BenchOptions {
+                csv: true,
+                ..parsed
+            },
+        ),
+        ["--iterations"] => Err("--iterations needs a value".to_string()),
+        [option, ..] => Err(format!("unknown option {option:?}")),
+    }
+}
+
+/// Runs `command`, reporting any errors as it goes, and returns whether it
+/// all went well.
+fn run(command: Command) -> bool {
+    match command {
+        Command::Run { days, options } => days.into_iter().fold(true, |ok, day| {
+            let input = options
+                .input
+                .clone()
+                .unwrap_or_else(|| Input::for_day(day.number));
+            let result = match options.dump {
+                Some(stage) => dump(day, &input, stage),
+                None => solve(day, &input, &options),
+            };
+            match result {
+                Ok(()) => ok,
+                Err(err) => {
+                    eprintln!("error: {}", err.render(&input.to_string()));
+                    false
+                }
+            }
+        }),
+        Command::Verify { manifest } => verify(&manifest),
+        Command::Bench { days, options } => bench(days, options),
+        Command::New { number } => match scaffold::create(Path::new("."), number) {
+            Ok(written) => {
+                for path in written {
+                    println!("wrote {}", path.display());
+                }
+                true
+            }
+            Err(err) => {
+                eprintln!("error: {err}");
+                false
+            }
+        },
+    }
+}
+
+fn solve(day: &Day, input: &Input, options: &RunOptions) -> solution::Result<()> {
+    for answer in (day.solve)(input, &options.parts)? {
+        if options.json {
+            println!("{}", answer_json(day, input, answer));
+        } else {
+            let Answer { part, value, .. } = answer;
+            println!("Day {} Part {part} answer: {value}", day.number);
+        }
+    }
+    Ok(())
+}
+
+/// Shows what `stage` makes of the input, under a heading naming the day.
+fn dump(day: &Day, input: &Input, stage: Stage) -> solution::Result<()> {
+    let output = (day.dump)(input, stage)?;
+    let stage = match stage {
+        Stage::Tokens => "tokens",
+        Stage::Ast => "AST",
+    };
+    println!("Day {} {stage}:\n{output}", day.number);
+    Ok(())
+}
+
+/// One line of `aoc run --json`. The answer is always a string, since not
+/// every puzzle's answer is a number.
+fn answer_json(day: &Day, input: &Input, answer: Answer) -> Value {
+    let Answer {
+        part,
+        value,
+        elapsed,
+    } = answer;
+    Value::object([
+        ("day", day.number.into()),
+        ("part", part.number().into()),
+        ("answer", value.into()),
+        ("elapsed_ns", elapsed.as_nanos().into()),
+        ("input", input.to_string().into()),
+    ])
+}
+
+/// Checks every answer in the manifest at `path`, printing a table of what
+/// passed and what didn't.
+fn verify(path: &Path) -> bool {
+    let entries = match manifest::read(path) {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!("error: {}", err.render(&path.display().to_string()));
+            return false;
+        }
+    };
+
+    let checks: Vec<Check> = entries.iter().map(check).collect();
+    print_checks(&checks);
+
+    for check in &checks {
+        if let Err(err) = &check.actual {
+            let Entry { day, part, .. } = check.entry;
+            eprintln!("\nerror: day {day} part {part}: {err}");
+        }
+    }
+    for day in DAYS {
+        if !entries.iter().any(|entry| entry.day == day.number) {
+            eprintln!("note: no expected answers for day {}", day.number);
+        }
+    }
+
+    let passed = checks.iter().filter(|check| check.passed()).count();
+    println!("\n{passed} passed, {} failed", checks.len() - passed);
+    passed == checks.len()
+}
+
+/// An entry of the manifest, and what its day actually answered.
+struct Check<'a> {
+    entry: &'a Entry,
+    /// The answer, or the rendered error that stopped the day.
+    actual: Result<String, String>,
+}
+
+impl Check<'_> {
+    fn passed(&self) -> bool {
+        self.actual.as_ref() == Ok(&self.entry.answer)
+    }
+
+    fn result(&self) -> &'static str {
+        match self.actual {
+            _ if self.passed() => "ok",
+            Ok(_) => "FAIL",
+            Err(_) => "ERROR",
+        }
+    }
+}
+
+fn check(entry: &Entry) -> Check<'_> {
+    let input = Input::File(entry.input.clone());
+    let actual = match days::find(entry.day) {
+        Some(day) => (day.solve)(&input, &[entry.part])
+            .map(|answers| answers.into_iter().map(|answer| answer.value).collect())
+            .map_err(|err| err.render(&input.to_string())),
+        None => Err(format!("day {} isn't solved yet", entry.day)),
+    };
+    Check { entry, actual }
+}
+
+fn print_checks(checks: &[Check]) {
+    let rows: Vec<[String; 6]> = checks
+        .iter()
+        .map(|check| {
+            let Entry {
+                day,
+                part,
+                input,
+                answer,
+            } = check.entry;
+            [
+                day.to_string(),
+                part.to_string(),
+                input.display().to_string(),
+                answer.clone(),
+                check.actual.clone().unwrap_or_else(|_| "-".to_string()),
+                check.result().to_string(),
+            ]
+        })
+        .collect();
+    print_table(
+        ["day", "part", "input", "expected", "actual", "result"],
+        &rows,
+    );
+}
+
+/// Times every phase of each of `days` on its usual input, printing a table
+/// of the fastest, median and slowest runs, or the same as CSV.
+fn bench(days: Vec<&'static Day>, options: BenchOptions) -> bool {
+    let BenchOptions { iterations, csv } = options;
+    let (rows, ok) = days
+        .into_iter()
+        .fold((Vec::new(), true), |(rows, ok), day| {
+            let input = Input::for_day(day.number);
+            match time_day(day, &input, iterations) {
+                Ok(phases) => {
+                    let rows = phases.into_iter().fold(rows, |rows, (phase, summary)| {
+                        append(rows, bench_row(day, phase, summary, csv))
+                    });
+                    (rows, ok)
+                }
+                Err(err) => {
+                    eprintln!("error: {}", err.render(&input.to_string()));
+                    (rows, false)
+                }
+            }
+        });
+
+    if csv {
+        println!("day,phase,iterations,min_ns,median_ns,max_ns");
+        for [day, phase, min, median, max] in rows {
+            println!("{day},{phase},{iterations},{min},{median},{max}");
+        }
+    } else {
+        println!("{iterations} iterations");
+        print_table(["day", "phase", "min", "median", "max"], &rows);
+    }
+    ok
+}
+
+/// Reads the input once, then runs the day on it `iterations` times.
+fn time_day(
+    day: &Day,
+    input: &Input,
+    iterations: usize,
+) -> solution::Result<Vec<(&'static str, Summary)>> {
+    let source = normalize(input.read()?);
+    let runs = (0..iterations)
+        .map(|_| (day.time)(&source))
+        .collect::<solution::Result<Vec<_>>>()?;
+    Ok(timing::summarize(&runs))
+}
+
+/// A phase's times, in nanoseconds for CSV, or made readable for a table.
+fn bench_row(day: &Day, phase: &str, summary: Summary, csv: bool) -> [String; 5] {
+    let Summary { min, median, max } = summary;
+    let [min, median, max] = [min, median, max].map(|duration| {
+        if csv {
+            duration.as_nanos().to_string()
+        } else {
+            format!("{duration:.1?}")
+        }
+    });
+    [day.number.to_string(), phase.to_string(), min, median, max]
+}
+
+/// Prints `rows` under `header`, with every column as wide as its widest cell.
+fn print_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) {
+    let header = header.map(String::from);
+    let widths = rows.iter().fold(
+        header.clone().map(|cell| cell.chars().count()),
+        |widths, row| std::array::from_fn(|i| widths[i].max(row[i].chars().count())),
+    );
+    for row in std::iter::once(&header).chain(rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
@@ -0,0 +1,278 @@
+use std::fmt::Display;
+
+use crate::diagnostics::Excerpt;
+use crate::functools::*;
+use crate::parsetools::{Location, ParseError, Span, Spanned};
+use crate::solution::{self, Error, Solution};
+use crate::timing;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    /// The calibration values as each part reads them. Only part two reads
+    /// spelled-out digits, so a line can have a value for one part and not
+    /// the other, and each reading keeps its own errors.
+    type Parsed = (Reading, Reading);
+
+    /// The tokens each part reads, one part after the other. They don't keep
+    /// their spans.
+    fn tokens(input: &str) -> solution::Result<Vec<String>> {
+        let part = |name: &str, is_part_two| {
+            let tokens = solution::debug_lines(&lex(input, is_part_two));
+            [vec![name.to_string()], tokens].concat()
+        };
+        Ok([part("part 1:", false), part("part 2:", true)].concat())
+    }
+
+    fn parse(input: &str) -> solution::Result<Self::Parsed> {
+        Ok((read(input, false), read(input, true)))
+    }
+
+    fn check((part1, part2): &Self::Parsed) -> solution::Result<()> {
+        let excerpts: Vec<Excerpt> = [part1, part2]
+            .into_iter()
+            .filter_map(|reading| reading.as_ref().err())
+            .flatten()
+            .cloned()
+            .collect();
+        if !excerpts.is_empty() {
+            return Err(Error::Input(excerpts));
+        }
+        Ok(())
+    }
+
+    fn part1(parsed: &Self::Parsed) -> solution::Result<impl Display> {
+        answer(&parsed.0)
+    }
+
+    fn part2(parsed: &Self::Parsed) -> solution::Result<impl Display> {
+        answer(&parsed.1)
+    }
+}
+
+type Reading = Result<Vec<Pair>, Vec<Excerpt>>;
+
+fn read(input: &str, is_part_two: bool) -> Reading {
+    let tokens = timing::phase("lex", || lex(input, is_part_two));
+    let (pairs, errors) = timing::phase("parse", || parse(&tokens));
+    if !errors.is_empty() {
+        return Err(Excerpt::all(input, locate(input, &tokens, errors)));
+    }
+    Ok(pairs)
+}
+
+fn answer(reading: &Reading) -> solution::Result<usize> {
+    match reading {
+        Ok(pairs) => Ok(eval(pairs)),
+        Err(excerpts) => Err(Error::Input(excerpts.clone())),
+    }
+}
+
+/// Points each of `errors` at the whole line its token is on, since these
+/// tokens don't keep their spans. Which line each token is on, and where
+/// each line starts, is worked out once for all of them.
+fn locate(input: &str, tokens: &Vec<Token>, errors: Vec<ParseError>) -> Vec<Spanned<ParseError>> {
+    let lines: Vec<usize> = tokens
+        .iter()
+        .scan(1, |line, token| {
+            let on = *line;
+            if matches!(token, Token::Newline) {
+                *line += 1;
+            }
+            Some(on)
+        })
+        .collect();
+    let offsets: Vec<usize> = std::iter::once(0)
+        .chain(input.match_indices('\n').map(|(offset, _)| offset + 1))
+        .collect();
+
+    errors
+        .into_iter()
+        .map(|error| {
+            let line = lines.get(error.pos()).copied().unwrap_or(offsets.len());
+            let offset = offsets.get(line - 1).copied().unwrap_or(input.len());
+            let text = input[offset..].lines().next().unwrap_or("");
+
+            let start = Location {
+                line,
+                column: 1,
+                offset,
+            };
+            let end = text.chars().fold(start, Location::advance);
+            Spanned::new(error, Span { start, end })
+        })
+        .collect()
+}
+
+fn lex(input: &str, is_part_two: bool) -> Vec<Token> {
+    input
+        .char_indices()
+        .fold((0, Vec::new()), |acc, c| {
+            let skip_count = acc.0;
+            if skip_count > 0 {
+                return (skip_count - 1, acc.1);
+            }
+
+            let pos = c.0;
+            let c = c.1;
+
+            let token = match c {
+                'a'..='z' => {
+                    if !is_part_two {
+                        return (0, acc.1);
+                    }
+
+                    use Token::*;
+                    let opt = [
+                        ("one", One),
+                        ("two", Two),
+                        ("three", Three),
+                        ("four", Four),
+                        ("five", Five),
+                        ("six", Six),
+                        ("seven", Seven),
+                        ("eight", Eight),
+                        ("nine", Nine),
+                    ]
+                    .into_iter()
+                    .find_map(|digit_str| {
+                        let (pos, exists) = lex_ident(input, pos, digit_str.0);
+                        if exists {
+                            Some((pos, digit_str.1))
+                        } else {
+                            None
+                        }
+                    });
+
+                    if let Some((end_pos, token)) = opt {
+                        return (end_pos - pos - 2, append(acc.1, token));
+                    } else {
+                        return acc;
+                    }
+                }
+                '\n' => Token::Newline,
+                // Only `1` to `9` are digits, so a `0` is skipped like a letter.
+                _ => match token_from_char(c) {
+                    Some(token) => token,
+                    None => return (0, acc.1),
+                },
+            };
+
+            (0, append(acc.1, token))
+        })
+        .1
+}
+
+fn lex_ident(input: &str, pos: usize, ident: &str) -> (usize, bool) {
+    (pos + ident.len(), input[pos..].starts_with(ident))
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Token {
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Newline,
+}
+
+fn token_from_char(c: char) -> Option<Token> {
+    use Token::*;
+    match c {
+        '1' => Some(One),
+        '2' => Some(Two),
+        '3' => Some(Three),
+        '4' => Some(Four),
+        '5' => Some(Five),
+        '6' => Some(Six),
+        '7' => Some(Seven),
+        '8' => Some(Eight),
+        '9' => Some(Nine),
+        _ => None,
+    }
+}
+
+fn token_to_usize(token: &Token) -> Option<usize> {
+    use Token::*;
+    match token {
+        One => Some(1),
+        Two => Some(2),
+        Three => Some(3),
+        Four => Some(4),
+        Five => Some(5),
+        Six => Some(6),
+        Seven => Some(7),
+        Eight => Some(8),
+        Nine => Some(9),
+        _ => None,
+    }
+}
+
+/// Reads a pair from every line it can. A line without a digit gets an error,
+/// and reading carries on from the next line.
+fn parse(tokens: &Vec<Token>) -> (Vec<Pair>, Vec<ParseError>) {
+    trampoline(
+        (0, None, (Vec::new(), Vec::new())),
+        |(pos, pair, (pairs, errors))| parse_recursive(tokens, pos, pair, pairs, errors),
+    )
+}
+
+type ParseState = (usize, Option<Pair>, (Vec<Pair>, Vec<ParseError>));
+
+fn parse_recursive(
+    input: &Vec<Token>,
+    pos: usize,
+    pair: Option<Pair>,
+    pairs: Vec<Pair>,
+    errors: Vec<ParseError>,
+) -> Bounce<ParseState, (Vec<Pair>, Vec<ParseError>)> {
+    if is_end(input, pos) {
+        return Bounce::Done((pairs, errors));
+    }
+
+    let token = input[pos];
+    use Token::*;
+    match token {
+        One | Two | Three | Four | Five | Six | Seven | Eight | Nine => {
+            let digit = token_to_usize(&token).unwrap();
+            if let Some(pair) = pair {
+                // Always overwrite the latter digit on the pair
+                Bounce::Recur((advance(pos), Some((pair.0, digit)), (pairs, errors)))
+            } else {
+                Bounce::Recur((advance(pos), Some((digit, digit)), (pairs, errors)))
+            }
+        }
+        Newline => {
+            if let Some(pair) = pair {
+                Bounce::Recur((advance(pos), None, (append(pairs, pair), errors)))
+            } else {
+                let error = ParseError::UnexpectedToken {
+                    pos,
+                    expected: "a digit".to_string(),
+                    found: format!("{token:?}"),
+                };
+                Bounce::Recur((advance(pos), None, (pairs, append(errors, error))))
+            }
+        }
+    }
+}
+
+type Pair = (usize, usize);
+
+fn eval(pairs: &Vec<Pair>) -> usize {
+    trampoline((0, 0), |(pos, sum)| eval_recursive(pairs, pos, sum))
+}
+
+fn eval_recursive(pairs: &Vec<Pair>, pos: usize, sum: usize) -> Bounce<(usize, usize), usize> {
+    if is_end(pairs, pos) {
+        return Bounce::Done(sum);
+    } else {
+        let pair = pairs[pos];
+        Bounce::Recur((advance(pos), sum + ((pair.0 * 10) + pair.1)))
+    }
+}
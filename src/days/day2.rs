@@ -0,0 +1,213 @@
+use std::fmt::Display;
+
+use crate::functools::*;
+use crate::parsetools::combinators::*;
+use crate::parsetools::lextools::Lexer;
+use crate::parsetools::{self as pt, ParseResult, Recovered, Spanned};
+use crate::solution::{self, Error, Solution};
+use crate::timing;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<Game>;
+
+    fn tokens(input: &str) -> solution::Result<Vec<String>> {
+        let (tokens, errors) = Token::lex_recovering(input);
+        if !errors.is_empty() {
+            return Err(Error::input(input, errors));
+        }
+        Ok(solution::debug_lines(&tokens))
+    }
+
+    fn parse(input: &str) -> solution::Result<Self::Parsed> {
+        let (tokens, lex_errors) = timing::phase("lex", || Token::lex_recovering(input));
+        let (games, parse_errors) = timing::phase("parse", || parse(tokens));
+        let errors = pt::merge_errors(lex_errors, parse_errors);
+        if !errors.is_empty() {
+            return Err(Error::input(input, errors));
+        }
+        Ok(games)
+    }
+
+    fn part1(games: &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(sum_possible_ids(games))
+    }
+
+    fn part2(games: &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(sum_power(games))
+    }
+}
+
+#[derive(Debug, Lexer)]
+enum Token {
+    #[regex_number]
+    Digit(usize),
+    #[token("red", Color::Red)]
+    #[token("green", Color::Green)]
+    #[token("blue", Color::Blue)]
+    Color(Color),
+    #[token(",")]
+    Comma,
+    #[token(":")]
+    Colon,
+    #[token(";")]
+    Semicolon,
+    #[token("\n")]
+    Newline,
+    #[token("Game")]
+    Game,
+}
+
+#[derive(Debug, Clone)]
+enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+/// Parses every game it can, skipping to the next line after a bad one.
+fn parse(tokens: Vec<Spanned<Token>>) -> Recovered<Vec<Game>> {
+    let (games, errors) = pt::transform_recovering(
+        tokens,
+        |token| pt::is_token(&Token::Newline, &token.node),
+        parse_game,
+    );
+    (games.into_iter().map(|game| game.node).collect(), errors)
+}
+
+fn parse_game(tokens: &Vec<Spanned<Token>>, pos: usize) -> ParseResult<(usize, Game)> {
+    let pos = pt::expect_token_at(tokens, pos, Token::Game)?;
+
+    let Some(Token::Digit(id)) = pt::token_at(tokens, pos) else {
+        return Err(pt::unexpected(tokens, pos, "a game id"));
+    };
+    let id = *id;
+    let pos = advance(pos);
+
+    let pos = pt::expect_token_at(tokens, pos, Token::Colon)?;
+
+    let (pos, sets) = parse_sets(tokens, pos)?;
+    Ok((pos, Game { id, sets }))
+}
+
+/// Cubes are separated by ",", sets by ";", and the last set ends the line.
+fn parse_sets(tokens: &Vec<Spanned<Token>>, pos: usize) -> ParseResult<(usize, Vec<Vec<Cubes>>)> {
+    let set = sep_by(parse_cubes, token(Token::Comma));
+    terminated_by(sep_by(set, token(Token::Semicolon)), token(Token::Newline))(tokens, pos)
+}
+
+fn parse_cubes(tokens: &Vec<Spanned<Token>>, pos: usize) -> ParseResult<(usize, Cubes)> {
+    let Some(Token::Digit(count)) = pt::token_at(tokens, pos) else {
+        return Err(pt::unexpected(tokens, pos, "a cube count"));
+    };
+    let pos = advance(pos);
+
+    let Some(Token::Color(color)) = pt::token_at(tokens, pos) else {
+        return Err(pt::unexpected(tokens, pos, "a color"));
+    };
+
+    Ok((
+        advance(pos),
+        Cubes {
+            count: *count,
+            color: color.clone(),
+        },
+    ))
+}
+
+#[derive(Debug)]
+pub struct Game {
+    id: usize,
+    sets: Vec<Vec<Cubes>>,
+}
+
+#[derive(Debug)]
+struct Cubes {
+    count: usize,
+    color: Color,
+}
+
+fn sum_possible_ids(games: &Vec<Game>) -> usize {
+    trampoline((0, 0), |(pos, acc)| {
+        sum_possible_ids_recursive(games, pos, acc)
+    })
+}
+
+fn sum_possible_ids_recursive(
+    games: &Vec<Game>,
+    pos: usize,
+    acc: usize,
+) -> Bounce<(usize, usize), usize> {
+    if is_end(games, pos) {
+        return Bounce::Done(acc);
+    }
+    let game = &games[pos];
+    let pos = advance(pos);
+
+    if is_possible(game) {
+        let acc = acc + game.id;
+        Bounce::Recur((pos, acc))
+    } else {
+        Bounce::Recur((pos, acc))
+    }
+}
+
+fn is_possible(game: &Game) -> bool {
+    !game.sets.iter().any(|set| {
+        set.iter().any(|cubes| match cubes.color {
+            Color::Red => cubes.count > 12,
+            Color::Green => cubes.count > 13,
+            Color::Blue => cubes.count > 14,
+        })
+    })
+}
+
+fn sum_power(games: &Vec<Game>) -> usize {
+    trampoline((0, 0), |(pos, accumulated_power)| {
+        sum_power_recursive(games, pos, accumulated_power)
+    })
+}
+
+fn sum_power_recursive(
+    games: &Vec<Game>,
+    pos: usize,
+    accumulated_power: usize,
+) -> Bounce<(usize, usize), usize> {
+    if is_end(games, pos) {
+        return Bounce::Done(accumulated_power);
+    }
+    let game = &games[pos];
+    let pos = advance(pos);
+
+    Bounce::Recur((pos, accumulated_power + calculate_power(game)))
+}
+
+fn calculate_power(game: &Game) -> usize {
+    let (red_min, green_min, blue_min) = game.sets.iter().fold((0, 0, 0), |acc, set| {
+        let (red_min, green_min, blue_min) =
+            set.iter().fold((0, 0, 0), |acc, cubes| match cubes.color {
+                Color::Red => (max(acc.0, cubes.count), acc.1, acc.2),
+                Color::Green => (acc.0, max(acc.1, cubes.count), acc.2),
+                Color::Blue => (acc.0, acc.1, max(acc.2, cubes.count)),
+            });
+
+        (
+            max(acc.0, red_min),
+            max(acc.1, green_min),
+            max(acc.2, blue_min),
+        )
+    });
+
+    red_min * green_min * blue_min
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if a > b {
+        a
+    } else if a < b {
+        b
+    } else {
+        a
+    }
+}
@@ -0,0 +1,317 @@
+use std::fmt::Display;
+
+use crate::functools::*;
+use crate::parsetools::lextools as lt;
+use crate::parsetools::{self as pt, ParseError, ParseResult};
+use crate::read;
+use crate::solution::{self, Error, Solution};
+use crate::timing;
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed = Schematic;
+
+    /// The symbols each part reads, with the numbers next to them. There is
+    /// no parser, so these are all there is to the schematic.
+    fn tokens(input: &str) -> solution::Result<Vec<String>> {
+        let Schematic { symbols, gears } = Self::parse(input)?;
+        let part = |name: &str, symbols: Vec<Symbol>| {
+            [vec![name.to_string()], solution::debug_lines(&symbols)].concat()
+        };
+        Ok([part("part 1:", symbols), part("part 2:", gears)].concat())
+    }
+
+    fn parse(input: &str) -> solution::Result<Self::Parsed> {
+        // Columns are found by counting chars, so the schematic has to be
+        // ASCII.
+        let input = &read::read_all_ascii(input.as_bytes())?;
+        if let Err(error) = check_width(input) {
+            return Err(Error::input(input, vec![lt::locate(input, error)]));
+        }
+        let chars: CharView = input.chars().collect();
+
+        let schematic = timing::phase("lex", || {
+            Ok(Schematic {
+                symbols: lex(&chars)?,
+                gears: lex_pt2(&chars)?,
+            })
+        });
+        schematic.map_err(|error| Error::input(input, vec![lt::locate(input, error)]))
+    }
+
+    fn part1(schematic: &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(sum(&schematic.symbols))
+    }
+
+    fn part2(schematic: &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(sum_pt2(&schematic.gears))
+    }
+}
+
+/// Every symbol with the numbers next to it, and the same for just the `*`s
+/// that could be gears.
+#[derive(Debug)]
+pub struct Schematic {
+    symbols: Vec<Symbol>,
+    gears: Vec<Symbol>,
+}
+
+/// Neighbours are found a line's length away, so every line has to be as
+/// long as the first.
+fn check_width(input: &str) -> ParseResult<()> {
+    let width = input.split_terminator('\n').next().map_or(0, str::len);
+    let ragged = input
+        .split_terminator('\n')
+        .scan(0, |start, line| {
+            let line_start = *start;
+            *start += line.len() + 1;
+            Some((line_start, line))
+        })
+        .find(|(_, line)| line.len() != width);
+
+    match ragged {
+        Some((start, line)) => Err(ParseError::UnexpectedToken {
+            pos: start + line.len().min(width),
+            expected: format!("{width} columns"),
+            found: line.len().to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn lex(input: &CharView) -> ParseResult<Vec<Symbol>> {
+    let accept: fn(char) -> bool = |c| c != '.' && !c.is_ascii_digit() && c != '\n';
+    try_trampoline((0, Vec::new()), |(pos, symbols)| {
+        lex_recursive(input, pos, accept, symbols)
+    })
+}
+
+fn lex_pt2(input: &CharView) -> ParseResult<Vec<Symbol>> {
+    try_trampoline((0, Vec::new()), |(pos, symbols)| {
+        lex_recursive(input, pos, |c| c == '*', symbols)
+    })
+}
+
+type LexState = (usize, Vec<Symbol>);
+
+fn lex_recursive(
+    input: &CharView,
+    pos: usize,
+    accept: fn(char) -> bool,
+    symbols: Vec<Symbol>,
+) -> ParseResult<Bounce<LexState, Vec<Symbol>>> {
+    if is_end(input, pos) {
+        return Ok(Bounce::Done(symbols));
+    }
+
+    let c = input[pos];
+    let (pos, symbols) = if accept(c) {
+        (advance(pos), append(symbols, new_symbol(input, pos)?))
+    } else {
+        (advance(pos), symbols)
+    };
+
+    Ok(Bounce::Recur((pos, symbols)))
+}
+
+fn new_symbol(input: &CharView, start_pos: usize) -> ParseResult<Symbol> {
+    let line_len = input
+        .clone()
+        .into_iter()
+        .collect::<String>()
+        .split("\n")
+        .next()
+        .unwrap()
+        .len()
+        + 1;
+    let line_count = input
+        .clone()
+        .into_iter()
+        .collect::<String>()
+        .split("\n")
+        .count()
+        - 1;
+    // let input = &input.clone().into_iter().filter(|c| c != &'\n').collect();
+
+    new_symbol_recursive(
+        input,
+        start_pos,
+        line_len,
+        line_count,
+        Direction::first(),
+        Vec::new(),
+    )
+}
+
+fn new_symbol_recursive(
+    input: &CharView,
+    start_pos: usize,
+    line_len: usize,
+    line_count: usize,
+    direction: Direction,
+    symbol: Symbol,
+) -> ParseResult<Symbol> {
+    if direction.is_end() {
+        return Ok(symbol);
+    }
+
+    let symbol = if let Some(pos) = direction.pos(start_pos, line_len, line_count) {
+        if let Some(number) = find_number(input, pos)? {
+            if !symbol.contains(&number) {
+                append(symbol, number)
+            } else {
+                symbol
+            }
+        } else {
+            symbol
+        }
+    } else {
+        symbol
+    };
+
+    new_symbol_recursive(
+        input,
+        start_pos,
+        line_len,
+        line_count,
+        direction.next(),
+        symbol,
+    )
+}
+
+/// The number whose digits run through `start_pos`, if there is one.
+fn find_number(input: &CharView, start_pos: usize) -> ParseResult<Option<usize>> {
+    let c = input[start_pos];
+    if !c.is_ascii_digit() {
+        return Ok(None);
+    }
+
+    let left: CharView = find_digit_bytes(input, start_pos, -1, |p| p - 1)
+        .into_iter()
+        .rev()
+        .collect();
+    let right = find_digit_bytes(input, start_pos, 1, |p| p + 1);
+
+    let start = start_pos - left.len();
+    let text: String = extend(extend(left, vec![c]), right).into_iter().collect();
+    pt::parse_integer(start, &text).map(Some)
+}
+
+fn find_digit_bytes(
+    input: &CharView,
+    start_pos: usize,
+    relative_pos: isize,
+    transform: fn(isize) -> isize,
+) -> CharView {
+    trampoline((relative_pos, Vec::new()), |(relative_pos, bytes)| {
+        find_digit_bytes_recursive(input, start_pos, relative_pos, transform, bytes)
+    })
+}
+
+fn find_digit_bytes_recursive(
+    input: &CharView,
+    start_pos: usize,
+    relative_pos: isize,
+    transform: fn(isize) -> isize,
+    bytes: CharView,
+) -> Bounce<(isize, CharView), CharView> {
+    let pos = start_pos as isize + relative_pos;
+    if is_end(input, pos as usize) || pos < 0 {
+        return Bounce::Done(bytes);
+    }
+
+    let c = input[pos as usize];
+    if !c.is_ascii_digit() {
+        return Bounce::Done(bytes);
+    }
+
+    Bounce::Recur((transform(relative_pos), append(bytes, c)))
+}
+
+type Symbol = Vec<usize>;
+type CharView = Vec<char>;
+
+enum Direction {
+    NorthWest,
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    End,
+}
+
+impl Direction {
+    fn first() -> Direction {
+        Direction::NorthWest
+    }
+    fn next(&self) -> Direction {
+        use Direction::*;
+        match self {
+            NorthWest => North,
+            North => NorthEast,
+            NorthEast => East,
+            East => SouthEast,
+            SouthEast => South,
+            South => SouthWest,
+            SouthWest => West,
+            West => End,
+            End => panic!("Tried going next for End"),
+        }
+    }
+
+    fn pos(&self, pos: usize, line_len: usize, line_count: usize) -> Option<usize> {
+        use Direction::*;
+        let x = pos % line_len;
+        let y = pos / line_len;
+        let (x_diff, y_diff): (isize, isize) = match self {
+            NorthWest => (-1, -1),
+            North => (0, -1),
+            NorthEast => (1, -1),
+            East => (1, 0),
+            SouthEast => (1, 1),
+            South => (0, 1),
+            SouthWest => (-1, 1),
+            West => (-1, 0),
+            End => panic!("Tried calculating position for End"),
+        };
+
+        let out_of_bounds_left = x_diff == -1 && x == 0;
+        if out_of_bounds_left {
+            return None;
+        }
+        let out_of_bounds_right = x_diff == 1 && (x >= line_len - 1);
+        if out_of_bounds_right {
+            return None;
+        }
+        let out_of_bounds_top = y_diff == -1 && y == 0;
+        if out_of_bounds_top {
+            return None;
+        }
+        let out_of_bounds_bottom = y_diff == 1 && (y >= line_count - 1);
+        if out_of_bounds_bottom {
+            return None;
+        }
+
+        Some(((pos as isize + x_diff) + (line_len as isize * y_diff)) as usize)
+    }
+    fn is_end(&self) -> bool {
+        matches!(self, Direction::End)
+    }
+}
+
+fn sum(symbols: &Vec<Symbol>) -> usize {
+    symbols
+        .iter()
+        .fold(0, |acc, s| acc + s.iter().sum::<usize>())
+}
+
+fn sum_pt2(symbols: &Vec<Symbol>) -> usize {
+    symbols
+        .iter()
+        .filter(|s| s.len() == 2)
+        .fold(0, |acc, s| acc + s.iter().product::<usize>())
+}
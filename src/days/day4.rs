@@ -0,0 +1,173 @@
+use std::fmt::Display;
+use std::io::BufRead;
+
+use crate::functools::*;
+use crate::parsetools::lextools::Lexer;
+use crate::parsetools::{self as pt, ParseResult, Spanned};
+use crate::solution::{self, Error, Solution};
+use crate::stream;
+use crate::timing;
+
+use lexer::Token;
+use parser::Card;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed = Vec<Card>;
+
+    fn tokens(input: &str) -> solution::Result<Vec<String>> {
+        let tokens: Vec<Spanned<Token>> =
+            stream::from_lexer(input.as_bytes()).collect::<Result<_, _>>()?;
+        Ok(solution::debug_lines(&tokens))
+    }
+
+    fn parse(input: &str) -> solution::Result<Self::Parsed> {
+        Self::parse_reader(input.as_bytes())
+    }
+
+    fn parse_reader(reader: impl BufRead) -> solution::Result<Self::Parsed> {
+        let tokens = stream::from_lexer(reader);
+        let newline = |token: &Token| pt::is_token(&Token::Newline, token);
+        let records = stream::records(tokens, newline, parser::parse_card);
+        let (cards, errors) = timing::phase("parse", || stream::collect(records))?;
+        if !errors.is_empty() {
+            return Err(Error::Input(errors));
+        }
+        Ok(cards)
+    }
+
+    fn part1(cards: &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(evaluator::eval_part1(cards))
+    }
+
+    fn part2(cards: &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(evaluator::eval_part2(cards))
+    }
+}
+
+mod lexer {
+    use super::*;
+
+    #[derive(Debug, Lexer)]
+    pub enum Token {
+        #[regex_number]
+        Number(usize),
+        #[token(":")]
+        Colon,
+        #[token("|")]
+        Pipe,
+        #[token("\n")]
+        Newline,
+        #[token("Card")]
+        Card,
+    }
+}
+
+mod parser {
+    use super::*;
+    use crate::parsetools::combinators::*;
+
+    #[derive(Debug)]
+    pub struct Card {
+        #[allow(dead_code)]
+        pub id: usize,
+        pub nums_winning: Vec<usize>,
+        pub nums_held: Vec<usize>,
+    }
+
+    /// "Card" Number ":" Number* "|" Number* "\n"
+    pub fn parse_card(tokens: &Vec<Spanned<Token>>, pos: usize) -> ParseResult<(usize, Card)> {
+        let header = terminated_by(sequence(token(Token::Card), number), token(Token::Colon));
+        let nums_winning = terminated_by(many(number), token(Token::Pipe));
+        let nums_held = terminated_by(many(number), token(Token::Newline));
+
+        let (pos, (((_, id), nums_winning), nums_held)) =
+            sequence(sequence(header, nums_winning), nums_held)(tokens, pos)?;
+
+        Ok((
+            pos,
+            Card {
+                id,
+                nums_winning,
+                nums_held,
+            },
+        ))
+    }
+
+    fn number(tokens: &Vec<Spanned<Token>>, pos: usize) -> ParseResult<(usize, usize)> {
+        match pt::token_at(tokens, pos) {
+            Some(Token::Number(number)) => Ok((advance(pos), *number)),
+            _ => Err(pt::unexpected(tokens, pos, "a number")),
+        }
+    }
+}
+
+mod evaluator {
+    use super::*;
+    use std::collections::HashSet;
+
+    pub fn eval_part1(cards: &Vec<Card>) -> usize {
+        return trampoline((0, 0), |(pos, points)| aux(cards, pos, points));
+
+        fn aux(cards: &Vec<Card>, pos: usize, points: usize) -> Bounce<(usize, usize), usize> {
+            if is_end(cards, pos) {
+                return Bounce::Done(points);
+            }
+
+            let card = &cards[pos];
+            Bounce::Recur((advance(pos), points + calculate_points(card)))
+        }
+    }
+
+    pub fn eval_part2(cards: &Vec<Card>) -> usize {
+        let points: Vec<usize> = cards.iter().map(calculate_matches).collect();
+        let instances: Vec<usize> = vec![1; cards.len()];
+
+        return trampoline((0, instances), |(pos, instances)| {
+            aux(cards, &points, pos, instances)
+        });
+
+        fn aux(
+            cards: &Vec<Card>,
+            points: &Vec<usize>,
+            pos: usize,
+            instances: Vec<usize>,
+        ) -> Bounce<(usize, Vec<usize>), usize> {
+            if is_end(cards, pos) {
+                return Bounce::Done(instances.into_iter().sum());
+            }
+
+            let multiplier = instances[pos];
+            let copy_ids: Vec<usize> = (0..points[pos]).map(|point| point + pos + 1).collect();
+            let instances = instances
+                .into_iter()
+                .enumerate()
+                .map(|(idx, instance)| {
+                    if copy_ids.contains(&idx) {
+                        instance + multiplier
+                    } else {
+                        instance
+                    }
+                })
+                .collect();
+
+            Bounce::Recur((advance(pos), instances))
+        }
+    }
+
+    fn calculate_points(card: &Card) -> usize {
+        let match_count = calculate_matches(card);
+        if match_count == 0 {
+            return 0;
+        }
+
+        let base: usize = 2;
+        base.pow((match_count - 1) as u32)
+    }
+    fn calculate_matches(card: &Card) -> usize {
+        let nums_winning: HashSet<usize> = HashSet::from_iter(card.nums_winning.clone());
+        let nums_held: HashSet<usize> = HashSet::from_iter(card.nums_held.clone());
+        nums_winning.intersection(&nums_held).count()
+    }
+}
@@ -0,0 +1,379 @@
+use std::fmt::Display;
+
+use crate::diagnostics::Excerpt;
+use crate::parsetools::lextools::Lexer;
+use crate::parsetools::{self as pt, ParseError, ParseResult, Recovered, Spanned};
+use crate::solution::{self, Error, Solution};
+use crate::timing;
+
+use crate::functools::*;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    /// The almanac, and its seeds read as ranges the way part 2 reads them.
+    type Parsed = (parser::Almanac, SeedRanges);
+
+    fn tokens(input: &str) -> solution::Result<Vec<String>> {
+        let (tokens, errors) = lexer::Token::lex_recovering(input);
+        if !errors.is_empty() {
+            return Err(Error::input(input, errors));
+        }
+        Ok(solution::debug_lines(&tokens))
+    }
+
+    fn parse(input: &str) -> solution::Result<Self::Parsed> {
+        let (tokens, lex_errors) = timing::phase("lex", || lexer::Token::lex_recovering(input));
+        let (almanac, parse_errors) = timing::phase("parse", || parser::parse(tokens));
+        let errors = pt::merge_errors(lex_errors, parse_errors);
+        if !errors.is_empty() {
+            return Err(Error::input(input, errors));
+        }
+        let ranges = seed_ranges(input, &almanac.initial);
+        Ok((almanac, ranges))
+    }
+
+    fn check((_, ranges): &Self::Parsed) -> solution::Result<()> {
+        match ranges {
+            Ok(_) => Ok(()),
+            Err(excerpts) => Err(Error::Input(excerpts.clone())),
+        }
+    }
+
+    fn part1((almanac, _): &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(evaluator::eval_part1(almanac.clone()))
+    }
+
+    fn part2((almanac, ranges): &Self::Parsed) -> solution::Result<impl Display> {
+        let ranges = ranges
+            .as_ref()
+            .map_err(|excerpts| Error::Input(excerpts.clone()))?;
+        Ok(evaluator::eval_part2(almanac.maps.clone(), ranges))
+    }
+}
+
+/// Seed ranges as `(start, len)`, or why there are none to answer part 2
+/// with. Part 1 reads the same numbers as plain seeds, so this isn't a parse
+/// error.
+type SeedRanges = Result<Vec<(usize, usize)>, Vec<Excerpt>>;
+
+/// Reads the seeds in pairs, leaving out empty ranges.
+fn seed_ranges(input: &str, initial: &parser::Initial) -> SeedRanges {
+    let ranges: Vec<(usize, usize)> = initial
+        .numbers
+        .chunks_exact(2)
+        .map(|range| (range[0], range[1]))
+        .filter(|(_, len)| *len > 0)
+        .collect();
+    if !ranges.is_empty() {
+        return Ok(ranges);
+    }
+
+    let error = ParseError::UnexpectedToken {
+        pos: 0,
+        expected: "a seed range that isn't empty".to_string(),
+        found: "none".to_string(),
+    };
+    Err(vec![Excerpt::new(input, Spanned::new(error, initial.span))])
+}
+
+mod lexer {
+    use super::*;
+
+    #[derive(Debug, Lexer)]
+    pub enum Token<'a> {
+        #[ident]
+        Ident(&'a str),
+        #[regex_number]
+        Number(usize),
+        #[token("-")]
+        Slash,
+        #[token(":")]
+        Colon,
+        #[token("\n")]
+        Newline,
+        #[token("to")]
+        To,
+        #[token("map")]
+        Map,
+    }
+}
+
+mod parser {
+    use super::lexer::Token;
+    use super::*;
+    use crate::parsetools::combinators::*;
+
+    #[derive(Debug, Clone)]
+    pub struct Almanac {
+        pub initial: Initial,
+        pub maps: Vec<Map>,
+    }
+
+    /// A line of the almanac.
+    #[derive(Debug, Clone)]
+    pub enum ASTNode {
+        Initial(Initial),
+        Map(Map),
+        Range(Range),
+    }
+
+    pub type Number = usize;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct Initial {
+        #[allow(dead_code)]
+        pub category: String,
+        pub numbers: Vec<Number>,
+        /// Where the numbers are, for errors about them.
+        pub span: pt::Span,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Range {
+        pub dest_start: usize,
+        pub src_start: usize,
+        pub len: usize,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Map {
+        #[allow(dead_code)]
+        pub src_category: String,
+        #[allow(dead_code)]
+        pub dest_category: String,
+        pub numbers: Vec<Range>,
+    }
+
+    /// Parses the almanac a line at a time, so every bad line gets its own
+    /// error. A map header starts a new map, and the ranges after it belong
+    /// to that map.
+    pub fn parse(tokens: Vec<Spanned<Token>>) -> Recovered<Almanac> {
+        // The almanac always opens with the initial categories.
+        let first_line = skip_newline(&tokens, 0);
+        let mut in_map = false;
+
+        let (nodes, errors) = pt::transform_recovering(
+            tokens,
+            |token| pt::is_token(&Token::Newline, &token.node),
+            |tokens, pos| {
+                let line = match pt::token_at(tokens, pos) {
+                    Some(Token::Newline) => return Ok((skip_newline(tokens, pos), None)),
+                    _ if pos == first_line => parse_initial(tokens, pos),
+                    Some(Token::Ident(_)) => {
+                        in_map = true;
+                        parse_map(tokens, pos)
+                    }
+                    Some(Token::Number(_)) if in_map => parse_range(tokens, pos),
+                    _ => Err(pt::unexpected(tokens, pos, "a map header")),
+                };
+                line.map(|(pos, node)| (pos, Some(node)))
+            },
+        );
+
+        let (initial, maps) = nodes.into_iter().filter_map(|node| node.node).fold(
+            (None, Vec::new()),
+            |(initial, maps), node| match node {
+                ASTNode::Initial(node) => (Some(node), maps),
+                ASTNode::Map(map) => (initial, append(maps, map)),
+                ASTNode::Range(range) => {
+                    let mut maps = maps;
+                    // After a bad header, which has its own error, ranges
+                    // go to the map before it, or nowhere.
+                    if let Some(map) = maps.last_mut() {
+                        map.numbers.push(range);
+                    }
+                    (initial, maps)
+                }
+            },
+        );
+
+        let errors = if initial.is_none() && errors.is_empty() {
+            let error = ParseError::UnexpectedEof {
+                pos: 0,
+                expected: "an initial category line".to_string(),
+            };
+            append(errors, Spanned::new(error, pt::Span::default()))
+        } else {
+            errors
+        };
+
+        let almanac = Almanac {
+            initial: initial.unwrap_or_default(),
+            maps,
+        };
+        (almanac, errors)
+    }
+
+    fn skip_newline(tokens: &Vec<Spanned<Token>>, pos: pt::Index) -> pt::Index {
+        return trampoline(pos, |pos| aux(tokens, pos));
+
+        fn aux(tokens: &Vec<Spanned<Token>>, pos: pt::Index) -> Bounce<pt::Index, pt::Index> {
+            if is_end(tokens, pos) || !pt::is_token(&Token::Newline, &tokens[pos].node) {
+                return Bounce::Done(pos);
+            }
+
+            Bounce::Recur(advance(pos))
+        }
+    }
+
+    fn parse_initial(
+        tokens: &Vec<Spanned<Token>>,
+        pos: pt::Index,
+    ) -> ParseResult<(pt::Index, ASTNode)> {
+        let category = parse_ident(tokens, pos)?;
+        let pos = advance(pos);
+
+        let pos = pt::expect_token_at(tokens, pos, Token::Colon)?;
+
+        let (end, numbers) = parse_numbers(tokens, pos);
+        if numbers.is_empty() {
+            return Err(pt::unexpected(tokens, pos, "a number"));
+        }
+        let span = pt::Span {
+            start: tokens[pos].span.start,
+            end: tokens[end - 1].span.end,
+        };
+
+        let pos = pt::expect_token_at(tokens, end, Token::Newline)?;
+
+        Ok((
+            pos,
+            ASTNode::Initial(Initial {
+                category,
+                numbers,
+                span,
+            }),
+        ))
+    }
+
+    fn parse_map(
+        tokens: &Vec<Spanned<Token>>,
+        pos: pt::Index,
+    ) -> ParseResult<(pt::Index, ASTNode)> {
+        let src_category = parse_ident(tokens, pos)?;
+        let pos = advance(pos);
+
+        let pos = pt::expect_token_at(tokens, pos, Token::Slash)?;
+
+        let pos = pt::expect_token_at(tokens, pos, Token::To)?;
+
+        let pos = pt::expect_token_at(tokens, pos, Token::Slash)?;
+
+        let dest_category = parse_ident(tokens, pos)?;
+        let pos = advance(pos);
+
+        let pos = pt::expect_token_at(tokens, pos, Token::Map)?;
+
+        let pos = pt::expect_token_at(tokens, pos, Token::Colon)?;
+
+        let pos = pt::expect_token_at(tokens, pos, Token::Newline)?;
+
+        Ok((
+            pos,
+            ASTNode::Map(Map {
+                src_category,
+                dest_category,
+                numbers: Vec::new(),
+            }),
+        ))
+    }
+
+    fn parse_ident(tokens: &Vec<Spanned<Token>>, pos: pt::Index) -> ParseResult<String> {
+        let Some(Token::Ident(ident)) = pt::token_at(tokens, pos) else {
+            return Err(pt::unexpected(tokens, pos, "a category name"));
+        };
+        Ok(ident.to_string())
+    }
+
+    /// A range is a line of exactly three numbers.
+    fn parse_range(
+        tokens: &Vec<Spanned<Token>>,
+        pos: pt::Index,
+    ) -> ParseResult<(pt::Index, ASTNode)> {
+        let range = terminated_by(
+            sequence(sequence(number, number), number),
+            token(Token::Newline),
+        );
+        let (pos, ((dest_start, src_start), len)) = range(tokens, pos)?;
+
+        Ok((
+            pos,
+            ASTNode::Range(Range {
+                dest_start,
+                src_start,
+                len,
+            }),
+        ))
+    }
+
+    fn number(tokens: &Vec<Spanned<Token>>, pos: pt::Index) -> ParseResult<(pt::Index, usize)> {
+        match pt::token_at(tokens, pos) {
+            Some(Token::Number(number)) => Ok((advance(pos), *number)),
+            _ => Err(pt::unexpected(tokens, pos, "a number")),
+        }
+    }
+
+    fn parse_numbers(tokens: &Vec<Spanned<Token>>, pos: pt::Index) -> (pt::Index, Vec<usize>) {
+        pt::parse_numbers(tokens, pos, Token::Number(0), |t| {
+            if let Token::Number(number) = t {
+                number
+            } else {
+                unreachable!()
+            }
+        })
+    }
+}
+
+mod evaluator {
+    use super::parser::Almanac;
+    use super::parser::Map;
+
+    type Mapper = Box<dyn Fn(usize) -> usize>;
+
+    /// The parser makes sure there is at least one seed.
+    pub fn eval_part1(almanac: Almanac) -> usize {
+        let mappers = maps_to_mappers(almanac.maps);
+        let seeds = almanac.initial.numbers;
+
+        seeds.into_iter().fold(usize::MAX, |acc, seed| {
+            acc.min(traverse_categories(seed, &mappers))
+        })
+    }
+
+    /// `ranges` are never empty, nor is any range in them.
+    pub fn eval_part2(maps: Vec<Map>, ranges: &Vec<(usize, usize)>) -> usize {
+        let mappers = maps_to_mappers(maps);
+
+        ranges.iter().fold(usize::MAX, |acc, &(start, len)| {
+            (start..(start + len)).fold(acc, |acc, seed| {
+                acc.min(traverse_categories(seed, &mappers))
+            })
+        })
+    }
+
+    fn maps_to_mappers(maps: Vec<Map>) -> Vec<Mapper> {
+        maps.into_iter()
+            .map(|map| {
+                let ranges = map.numbers;
+                Box::new(move |n| {
+                    let Some(index) = ranges
+                        .iter()
+                        .position(|range| n >= range.src_start && n < range.src_start + range.len)
+                    else {
+                        return n;
+                    };
+
+                    let range = &ranges[index];
+
+                    let start_distance = n - range.src_start;
+                    range.dest_start + start_distance
+                }) as Mapper
+            })
+            .collect()
+    }
+
+    fn traverse_categories(seed: usize, mappers: &Vec<Mapper>) -> usize {
+        mappers.iter().fold(seed, |acc, f| f(acc))
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt::Display;
+
+use crate::functools::is_end;
+use crate::parsetools::lextools::Lexer;
+use crate::parsetools::{self as pt, ParseError, ParseResult, Spanned, SpannedResult};
+use crate::solution::{self, Error, Solution};
+use crate::timing;
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    /// Every race's duration, and every record distance, in the order
+    /// they're listed.
+    type Parsed = (Vec<parser::Duration>, Vec<parser::Distance>);
+
+    fn tokens(input: &str) -> solution::Result<Vec<String>> {
+        let tokens = lexer::Token::lex(input).map_err(|err| Error::input(input, vec![err]))?;
+        Ok(solution::debug_lines(&tokens))
+    }
+
+    fn parse(input: &str) -> solution::Result<Self::Parsed> {
+        let tokens = timing::phase("lex", || lexer::Token::lex(input))
+            .map_err(|err| Error::input(input, vec![err]))?;
+        timing::phase("parse", || parser::parse(&tokens))
+            .map_err(|err| Error::input(input, vec![err]))
+    }
+
+    fn part1((durations, distances): &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(evaluator::eval(&parser::races(durations, distances)))
+    }
+
+    fn part2((durations, distances): &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(evaluator::calc_record_beaters(&parser::joined_race(
+            durations, distances,
+        )))
+    }
+}
+
+mod lexer {
+    use super::*;
+
+    #[derive(Debug, Lexer)]
+    pub enum Token {
+        #[regex_number]
+        Number(usize),
+        #[token(":")]
+        Colon,
+        #[token("\n")]
+        Newline,
+        #[token("Time")]
+        Time,
+        #[token("Distance")]
+        Distance,
+    }
+}
+
+mod parser {
+    use super::lexer::Token;
+    use super::*;
+
+    pub type Duration = usize;
+    pub type Distance = usize;
+
+    #[derive(Debug)]
+    pub struct Race {
+        pub duration: Duration,
+        pub record_distance: Distance,
+    }
+
+    pub fn parse(tokens: &Vec<Spanned<Token>>) -> SpannedResult<(Vec<Duration>, Vec<Distance>)> {
+        parse_sections(tokens).map_err(|err| pt::locate(tokens, err))
+    }
+
+    /// Every race needs both a duration and a record, so the two lines must
+    /// list as many numbers each. Part 2 reads each line as one big number,
+    /// so a line whose digits don't fit once joined is as bad as a number
+    /// that doesn't.
+    fn parse_sections(tokens: &Vec<Spanned<Token>>) -> ParseResult<(Vec<Duration>, Vec<Distance>)> {
+        let durations_pos = 0;
+
+        let (distances_pos, durations) = parse_durations(tokens, durations_pos)?;
+        let (end, distances) = parse_distances(tokens, distances_pos)?;
+        if !is_end(tokens, end) {
+            return Err(pt::unexpected(tokens, end, "the end of the input"));
+        }
+        if distances.len() != durations.len() {
+            return Err(ParseError::UnexpectedToken {
+                pos: distances_pos,
+                expected: format!("{} distances, one per race", durations.len()),
+                found: distances.len().to_string(),
+            });
+        }
+
+        concat_numbers(durations_pos, &durations)?;
+        concat_numbers(distances_pos, &distances)?;
+
+        Ok((durations, distances))
+    }
+
+    pub fn races(durations: &[Duration], distances: &[Distance]) -> Vec<Race> {
+        durations
+            .iter()
+            .zip(distances)
+            .map(|(&duration, &distance)| Race {
+                duration,
+                record_distance: distance,
+            })
+            .collect()
+    }
+
+    /// Part 2 reads each line as one big number, ignoring the spaces.
+    pub fn joined_race(durations: &[Duration], distances: &[Distance]) -> Race {
+        let joined = |numbers| concat_numbers(0, numbers).expect("parse checks joined numbers fit");
+        Race {
+            duration: joined(durations),
+            record_distance: joined(distances),
+        }
+    }
+
+    fn parse_durations(
+        tokens: &Vec<Spanned<Token>>,
+        pos: pt::Index,
+    ) -> ParseResult<(pt::Index, Vec<Duration>)> {
+        parse_section(tokens, pos, Token::Time)
+    }
+
+    fn parse_distances(
+        tokens: &Vec<Spanned<Token>>,
+        pos: pt::Index,
+    ) -> ParseResult<(pt::Index, Vec<Distance>)> {
+        parse_section(tokens, pos, Token::Distance)
+    }
+
+    fn parse_section(
+        tokens: &Vec<Spanned<Token>>,
+        pos: pt::Index,
+        first_token: Token,
+    ) -> ParseResult<(pt::Index, Vec<Distance>)> {
+        let pos = pt::expect_token_at(tokens, pos, first_token)?;
+        let pos = pt::expect_token_at(tokens, pos, Token::Colon)?;
+        let (end, numbers) = parse_numbers(tokens, pos);
+        if numbers.is_empty() {
+            return Err(pt::unexpected(tokens, pos, "a number"));
+        }
+        let pos = pt::expect_token_at(tokens, end, Token::Newline)?;
+        Ok((pos, numbers))
+    }
+
+    fn parse_numbers(tokens: &Vec<Spanned<Token>>, pos: pt::Index) -> (pt::Index, Vec<usize>) {
+        pt::parse_numbers(tokens, pos, Token::Number(0), |t| {
+            if let Token::Number(number) = t {
+                number
+            } else {
+                unreachable!()
+            }
+        })
+    }
+
+    /// Joins the digits of every number in a section, with `pos` pointing at
+    /// the section so an overflowing result can be reported.
+    fn concat_numbers(pos: pt::Index, numbers: &[usize]) -> ParseResult<usize> {
+        let text = numbers
+            .iter()
+            .fold(String::new(), |acc, number| acc + &number.to_string());
+        pt::parse_integer(pos, &text)
+    }
+}
+
+mod evaluator {
+    use super::parser::{Distance, Duration, Race};
+
+    pub fn eval(races: &Vec<Race>) -> usize {
+        races.iter().map(calc_record_beaters).product()
+    }
+
+    pub fn calc_record_beaters(race: &Race) -> usize {
+        (0..=race.duration)
+            .filter(|hold_duration| {
+                calc_distance_travelled(race, *hold_duration)
+                    .is_none_or(|distance| distance > race.record_distance)
+            })
+            .count()
+    }
+
+    /// `None` for a distance too far to count, which beats any record.
+    fn calc_distance_travelled(race: &Race, hold_duration: Duration) -> Option<Distance> {
+        let boat_speed_per_second = hold_duration;
+
+        let remaining_duration = race.duration - hold_duration;
+
+        boat_speed_per_second.checked_mul(remaining_duration)
+    }
+}
@@ -1,33 +1,50 @@
-use std::error::Error;
+use std::fmt::Display;
+use std::io::BufRead;
 
-#[path = "../read.rs"]
-mod read;
-use crate::parser::Hand;
-use crate::read::read;
-
-#[path = "../parsetools.rs"]
-mod parsetools;
-use crate::parsetools as pt;
 use crate::parsetools::lextools as lt;
-use crate::parsetools::Index;
+use crate::parsetools::{self as pt, Index, ParseError, ParseResult, Spanned};
+use crate::solution::{self, Error, Solution};
+use crate::tail_end;
+use crate::timing;
 
-#[path = "../functools.rs"]
-mod functools;
 use crate::functools::*;
+use crate::stream;
+
+use lexer::Token;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let input = read("./input/day7.txt")?;
+pub struct Day7;
 
-    let tokens = lexer::lex(input);
-    let hands = parser::parse(&tokens);
+impl Solution for Day7 {
+    type Parsed = Vec<parser::Hand>;
+
+    fn tokens(input: &str) -> solution::Result<Vec<String>> {
+        let tokens: Vec<Spanned<Token>> =
+            stream::lex(input.as_bytes(), lexer::step).collect::<Result<_, _>>()?;
+        Ok(solution::debug_lines(&tokens))
+    }
 
-    let part1_total_winnings = eval_part1::eval(&hands);
-    println!("Day 7 Part 1 answer: {part1_total_winnings}");
+    fn parse(input: &str) -> solution::Result<Self::Parsed> {
+        Self::parse_reader(input.as_bytes())
+    }
+
+    fn parse_reader(reader: impl BufRead) -> solution::Result<Self::Parsed> {
+        let tokens = stream::lex(reader, lexer::step);
+        let newline = |token: &Token| pt::is_token(&Token::Newline, token);
+        let records = stream::records(tokens, newline, parser::parse_hand);
+        let (hands, errors) = timing::phase("parse", || stream::collect(records))?;
+        if !errors.is_empty() {
+            return Err(Error::Input(errors));
+        }
+        Ok(hands)
+    }
 
-    let part2_total_winnings = eval_part2::eval(&hands);
-    println!("Day 7 Part 1 answer: {part2_total_winnings}");
+    fn part1(hands: &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(eval_part1::eval(hands))
+    }
 
-    Ok(())
+    fn part2(hands: &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(eval_part2::eval(hands))
+    }
 }
 
 mod lexer {
@@ -36,23 +53,28 @@ mod lexer {
     #[derive(Debug)]
     pub enum Token {
         Char(char),
+        Number(usize),
         /// ' '
         Space,
         /// '\n'
         Newline,
     }
 
-    pub fn lex(input: Vec<char>) -> Vec<Token> {
-        pt::transform(input, |input, pos| {
-            use Token::*;
-            let c = input[pos];
-            match c {
-                '0'..='9' | 'A' | 'K' | 'Q' | 'J' | 'T' => (advance(pos), Char(c)),
-                ' ' => (advance(pos), Space),
-                '\n' => (advance(pos), Newline),
-                _ => panic!("Unknown character at {pos}: {c}"),
-            }
-        })
+    /// A bid is all that follows a space, so digits after one are read as a
+    /// whole number rather than as card labels.
+    pub fn step(input: &str, pos: Index) -> ParseResult<(Index, Token)> {
+        use Token::*;
+        let c = lt::peek(input, pos)?;
+        if c.is_ascii_digit() && input[..pos].ends_with(' ') {
+            let (pos, number) = lt::read_number(input, pos)?;
+            return Ok((pos, Number(number)));
+        }
+        match c {
+            '0'..='9' | 'A' | 'K' | 'Q' | 'J' | 'T' => Ok((advance(pos), Char(c))),
+            ' ' => Ok((advance(pos), Space)),
+            '\n' => Ok((advance(pos), Newline)),
+            _ => Err(ParseError::UnknownCharacter { pos, c }),
+        }
     }
 }
 
@@ -109,60 +131,54 @@ mod parser {
     pub type BidAmount = usize;
     pub type FiveLabels = [Label; 5];
 
-    pub fn parse(tokens: &Vec<Token>) -> Vec<Hand> {
-        return aux(tokens, 0, Vec::new());
+    pub fn parse_hand(tokens: &Vec<Spanned<Token>>, pos: Index) -> ParseResult<(Index, Hand)> {
+        let (pos, cards) = parse_cards(tokens, pos)?;
 
-        fn aux(tokens: &Vec<Token>, pos: Index, hands: Vec<Hand>) -> Vec<Hand> {
-            tail_end!(tokens[pos], return hands);
+        let pos = pt::expect_token_at(tokens, pos, Token::Space)?;
 
-            let (pos, hand) = parse_hand(tokens, pos);
+        let (pos, bid) = parse_number(tokens, pos)?;
 
-            aux(tokens, pos, append(hands, hand))
-        }
-    }
-
-    fn parse_hand(tokens: &Vec<Token>, pos: Index) -> (Index, Hand) {
-        let (pos, cards) = parse_cards(tokens, pos);
-
-        let pos = pt::expect_token_at(tokens, pos, Token::Space);
-
-        let (pos, bid) = parse_number(tokens, pos);
-
-        let pos = pt::expect_token_at(tokens, pos, Token::Newline);
+        let pos = pt::expect_token_at(tokens, pos, Token::Newline)?;
 
         let hand = Hand { cards, bid };
 
-        (pos, hand)
+        Ok((pos, hand))
     }
 
-    fn parse_cards(tokens: &Vec<Token>, pos: Index) -> (Index, FiveLabels) {
-        let (pos, labels) = aux(tokens, pos, 0, Vec::new());
+    fn parse_cards(tokens: &Vec<Spanned<Token>>, pos: Index) -> ParseResult<(Index, FiveLabels)> {
+        let (pos, labels) = try_trampoline((pos, 0, Vec::new()), |(pos, relative_pos, labels)| {
+            aux(tokens, pos, relative_pos, labels)
+        })?;
+
+        type State = (Index, usize, Vec<Label>);
 
         fn aux(
-            tokens: &Vec<Token>,
+            tokens: &Vec<Spanned<Token>>,
             pos: Index,
             relative_pos: usize,
             labels: Vec<Label>,
-        ) -> (Index, Vec<Label>) {
-            tail_end!(tokens[pos], if relative_pos >= 5, return (pos, labels));
-
-            let Token::Char(c) = &tokens[pos]
-            else { panic!("expected Char token, found {:?}", &tokens[pos]); };
-            let label = Label::try_from(*c).unwrap();
+        ) -> ParseResult<Bounce<State, (Index, Vec<Label>)>> {
+            tail_end!(tokens[pos], if relative_pos >= 5, return Ok(Bounce::Done((pos, labels))));
+
+            let Some(label) = (match &tokens[pos].node {
+                Token::Char(c) => Label::try_from(*c),
+                _ => None,
+            }) else {
+                return Err(pt::unexpected(tokens, pos, "a card label"));
+            };
 
-            aux(
-                tokens,
+            Ok(Bounce::Recur((
                 advance(pos),
                 advance(relative_pos),
                 append(labels, label),
-            )
+            )))
         }
 
         if labels.len() != 5 {
-            panic!("Expected label length of 5, found {}", labels.len());
+            return Err(pt::unexpected(tokens, pos, "a card label"));
         }
 
-        return (
+        return Ok((
             pos,
             [
                 labels[0].clone(),
@@ -171,27 +187,19 @@ mod parser {
                 labels[3].clone(),
                 labels[4].clone(),
             ],
-        );
+        ));
     }
 
-    fn parse_number(tokens: &Vec<Token>, pos: Index) -> (Index, usize) {
-        return aux(tokens, pos, 0);
-
-        fn aux(tokens: &Vec<Token>, pos: Index, number: usize) -> (Index, usize) {
-            tail_end!(tokens[pos], return (pos, number));
-
-            let (pos, Token::Char(c)) = (advance(pos), &tokens[pos])
-            else { return (pos, number); };
-
-            let digit = c.to_digit(10).unwrap() as usize;
-
-            aux(tokens, pos, (number * 10) + digit)
+    fn parse_number(tokens: &Vec<Spanned<Token>>, pos: Index) -> ParseResult<(Index, usize)> {
+        match pt::token_at(tokens, pos) {
+            Some(Token::Number(number)) => Ok((advance(pos), *number)),
+            _ => Err(pt::unexpected(tokens, pos, "a bid")),
         }
     }
 }
 
 mod eval_common {
-    use crate::parser::Hand;
+    use super::parser::Hand;
 
     #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
     pub enum HandType {
@@ -211,7 +219,7 @@ mod eval_common {
     }
 
     pub fn eval_base(hands: &Vec<Hand>) -> usize {
-        let mut hands: Vec<_> = hands.into_iter().map(|hand| hand.clone()).collect();
+        let mut hands: Vec<_> = hands.iter().collect();
         hands.sort_by(|a, b| (*a).partial_cmp(*b).unwrap());
 
         hands
@@ -225,7 +233,7 @@ mod eval_part1 {
     use std::cmp::Ordering;
     use std::collections::HashMap;
 
-    use crate::parser::FiveLabels;
+    use super::parser::FiveLabels;
 
     use super::eval_common::{eval_base, HandType};
     use super::parser::Hand;
@@ -277,7 +285,7 @@ mod eval_part1 {
                 occurence_map.insert(label, count);
             }
 
-            let mut occ: Vec<usize> = occurence_map.into_iter().map(|(_, count)| count).collect();
+            let mut occ: Vec<usize> = occurence_map.into_values().collect();
             occ.sort_by(|a, b| b.cmp(a));
 
             use HandType::*;
@@ -313,7 +321,7 @@ mod eval_part2 {
     use std::cmp::Ordering;
     use std::collections::HashMap;
 
-    use crate::parser::{FiveLabels, Label};
+    use super::parser::{FiveLabels, Label};
 
     use super::eval_common::HandType;
     use super::parser::Hand;
@@ -331,16 +339,8 @@ mod eval_part2 {
                 let mut ordering = None;
 
                 for (first_label, second_label) in first.cards.iter().zip(second.cards.iter()) {
-                    let first_is_j = if let Label::J = first_label {
-                        true
-                    } else {
-                        false
-                    };
-                    let second_is_j = if let Label::J = second_label {
-                        true
-                    } else {
-                        false
-                    };
+                    let first_is_j = matches!(first_label, Label::J);
+                    let second_is_j = matches!(second_label, Label::J);
 
                     if !first_is_j && second_is_j {
                         ordering = Some(Ordering::Greater);
@@ -389,10 +389,10 @@ mod eval_part2 {
             occurence_map.insert(label, count);
         }
 
-        let mut occ: Vec<usize> = occurence_map.into_iter().map(|(_, count)| count).collect();
+        let mut occ: Vec<usize> = occurence_map.into_values().collect();
         occ.sort_by(|a, b| b.cmp(a));
 
-        if let Some(_) = occ.get(0) {
+        if !occ.is_empty() {
             occ[0] += joker_count;
         } else {
             occ.push(joker_count);
@@ -423,8 +423,8 @@ mod eval_part2 {
     }
 
     pub fn eval(hands: &Vec<Hand>) -> usize {
-        let mut hands: Vec<_> = hands.into_iter().map(|hand| hand.clone()).collect();
-        hands.sort_by(|a, b| cmp_hand(*a, *b).unwrap());
+        let mut hands: Vec<_> = hands.iter().collect();
+        hands.sort_by(|a, b| cmp_hand(a, b).unwrap());
 
         hands
             .into_iter()
@@ -0,0 +1,185 @@
+//! Every day's puzzle, and the registry the `aoc` runner finds them in.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::read::{normalize, Input};
+use crate::solution::{self, Solution};
+use crate::timing::{self, Timings};
+
+// The days take `&Vec<T>` token lists, and some end in `return aux(..)`.
+#[allow(clippy::ptr_arg, clippy::needless_return)]
+pub mod day1;
+#[allow(clippy::ptr_arg)]
+pub mod day2;
+#[allow(clippy::ptr_arg)]
+pub mod day3;
+#[allow(clippy::ptr_arg)]
+pub mod day4;
+#[allow(clippy::ptr_arg)]
+pub mod day5;
+#[allow(clippy::ptr_arg)]
+pub mod day6;
+#[allow(clippy::ptr_arg, clippy::needless_return)]
+pub mod day7;
+
+/// One of the two parts of a day's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> usize {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A part's answer, and how long it took to get: reading and parsing the
+/// input, which every part shares, plus answering the part itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub part: Part,
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+/// Reads a day's input and answers each of the given parts, in the same
+/// order. The input is parsed once, however many parts are asked for.
+pub type Solve = fn(&Input, &[Part]) -> solution::Result<Vec<Answer>>;
+
+/// The `Solve` for `S`.
+pub fn solve<S: Solution>(input: &Input, parts: &[Part]) -> solution::Result<Vec<Answer>> {
+    let start = Instant::now();
+    let parsed = S::parse_reader(input.reader()?)?;
+    let parsing = start.elapsed();
+
+    parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let value = match part {
+                Part::One => S::part1(&parsed).map(|answer| answer.to_string()),
+                Part::Two => S::part2(&parsed).map(|answer| answer.to_string()),
+            }?;
+            let elapsed = parsing + start.elapsed();
+            Ok(Answer {
+                part,
+                value,
+                elapsed,
+            })
+        })
+        .collect()
+}
+
+/// Runs a day once on an input already read and normalized, and times each
+/// phase: the lexing and parsing the day marks, then each part.
+pub type Time = fn(&str) -> solution::Result<Timings>;
+
+/// The `Time` for `S`.
+pub fn time<S: Solution>(input: &str) -> solution::Result<Timings> {
+    let (result, timings) = timing::record(|| {
+        let parsed = S::parse(input)?;
+        timing::phase("part 1", || {
+            S::part1(&parsed).map(|answer| answer.to_string())
+        })?;
+        timing::phase("part 2", || {
+            S::part2(&parsed).map(|answer| answer.to_string())
+        })?;
+        Ok(())
+    });
+    result.map(|()| timings)
+}
+
+/// A stage of reading the input that `aoc run` can stop after, to show what
+/// it made of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Tokens,
+    Ast,
+}
+
+/// Reads a day's input and shows what `stage` makes of it, without answering
+/// either part.
+pub type Dump = fn(&Input, Stage) -> solution::Result<String>;
+
+/// The `Dump` for `S`.
+pub fn dump<S: Solution>(input: &Input, stage: Stage) -> solution::Result<String> {
+    match stage {
+        Stage::Tokens => Ok(S::tokens(&normalize(input.read()?))?.join("\n")),
+        Stage::Ast => {
+            let parsed = S::parse_reader(input.reader()?)?;
+            S::check(&parsed)?;
+            Ok(format!("{parsed:#?}"))
+        }
+    }
+}
+
+pub struct Day {
+    pub number: usize,
+    pub solve: Solve,
+    pub time: Time,
+    pub dump: Dump,
+}
+
+/// Every day that can be run, in order. A new day is added here.
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        solve: solve::<day1::Day1>,
+        time: time::<day1::Day1>,
+        dump: dump::<day1::Day1>,
+    },
+    Day {
+        number: 2,
+        solve: solve::<day2::Day2>,
+        time: time::<day2::Day2>,
+        dump: dump::<day2::Day2>,
+    },
+    Day {
+        number: 3,
+        solve: solve::<day3::Day3>,
+        time: time::<day3::Day3>,
+        dump: dump::<day3::Day3>,
+    },
+    Day {
+        number: 4,
+        solve: solve::<day4::Day4>,
+        time: time::<day4::Day4>,
+        dump: dump::<day4::Day4>,
+    },
+    Day {
+        number: 5,
+        solve: solve::<day5::Day5>,
+        time: time::<day5::Day5>,
+        dump: dump::<day5::Day5>,
+    },
+    Day {
+        number: 6,
+        solve: solve::<day6::Day6>,
+        time: time::<day6::Day6>,
+        dump: dump::<day6::Day6>,
+    },
+    Day {
+        number: 7,
+        solve: solve::<day7::Day7>,
+        time: time::<day7::Day7>,
+        dump: dump::<day7::Day7>,
+    },
+];
+
+pub fn find(number: usize) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
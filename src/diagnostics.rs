@@ -0,0 +1,107 @@
+use std::fmt::Write;
+
+use crate::parsetools::{ParseError, Span, Spanned};
+
+/// How many lines to show above and below the offending one.
+pub const CONTEXT_LINES: usize = 2;
+
+/// Renders `error` against the `source` it was found in, rustc style:
+///
+/// ```text
+/// expected Colon, found Number(3)
+///  --> input/day5.txt:3:18
+///   |
+/// 1 | seeds: 79 14 55 13
+/// 2 |
+/// 3 | seed-to-soil map 3
+///   |                  ^
+/// 4 | 50 98 2
+/// ```
+///
+/// The first line is the bare message, so callers can prefix it with `error: `.
+pub fn render(path: &str, source: &str, error: &Spanned<ParseError>) -> String {
+    render_excerpt(path, source, 1, error)
+}
+
+/// Like `render`, when only part of the source is at hand: `excerpt` holds
+/// the source from line `first_line` on, and the context shown is limited to
+/// what it covers.
+pub fn render_excerpt(
+    path: &str,
+    excerpt: &str,
+    first_line: usize,
+    error: &Spanned<ParseError>,
+) -> String {
+    let lines: Vec<&str> = excerpt.lines().collect();
+    let Span { start, end } = error.span;
+
+    let first = start.line.saturating_sub(CONTEXT_LINES).max(first_line);
+    let last = (start.line + CONTEXT_LINES).min((first_line + lines.len()).max(start.line + 1) - 1);
+    let gutter = last.to_string().len();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", error.node);
+    let _ = writeln!(out, "{:gutter$}--> {path}:{start}", "");
+    let _ = writeln!(out, "{:gutter$} |", "");
+
+    for number in first..=last {
+        let line = lines.get(number - first_line).copied().unwrap_or("");
+        let _ = writeln!(out, "{}", format!("{number:>gutter$} | {line}").trim_end());
+        if number == start.line {
+            let width = if end.line == start.line {
+                end.column.saturating_sub(start.column)
+            } else {
+                (line.chars().count() + 1).saturating_sub(start.column) + 1
+            };
+            let indent = " ".repeat(start.column - 1);
+            let carets = "^".repeat(width.max(1));
+            let _ = writeln!(out, "{:gutter$} | {indent}{carets}", "");
+        }
+    }
+
+    out.trim_end_matches(['\n', ' ']).to_string()
+}
+
+/// An error together with the lines of source `render` would show around it,
+/// so it can still be rendered once the source itself is gone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Excerpt {
+    pub error: Spanned<ParseError>,
+    /// The line `text` starts on.
+    pub first_line: usize,
+    pub text: String,
+}
+
+impl Excerpt {
+    pub fn new(source: &str, error: Spanned<ParseError>) -> Excerpt {
+        Excerpt::from_lines(&source.lines().collect::<Vec<_>>(), error)
+    }
+
+    /// Excerpts for all of `errors`, splitting `source` into lines just once.
+    pub fn all(source: &str, errors: Vec<Spanned<ParseError>>) -> Vec<Excerpt> {
+        let lines: Vec<&str> = source.lines().collect();
+        errors
+            .into_iter()
+            .map(|error| Excerpt::from_lines(&lines, error))
+            .collect()
+    }
+
+    fn from_lines(lines: &[&str], error: Spanned<ParseError>) -> Excerpt {
+        let first_line = error.span.start.line.saturating_sub(CONTEXT_LINES).max(1);
+        let text = lines
+            .iter()
+            .skip(first_line - 1)
+            .take(error.span.start.line + CONTEXT_LINES + 1 - first_line)
+            .map(|line| format!("{line}\n"))
+            .collect();
+        Excerpt {
+            error,
+            first_line,
+            text,
+        }
+    }
+
+    pub fn render(&self, path: &str) -> String {
+        render_excerpt(path, &self.text, self.first_line, &self.error)
+    }
+}
@@ -1,14 +1,17 @@
 use std::iter::once;
 
 #[inline]
-pub fn is_end<T>(input: &Vec<T>, pos: usize) -> bool {
+pub fn is_end<T>(input: &[T], pos: usize) -> bool {
     input.len() <= pos
 }
 
-/// append works exactly like Go's `append` function.
+/// append works exactly like Go's `append` function: it takes the collection
+/// by value and hands it back with `elem` pushed onto its existing allocation,
+/// so accumulating with it is amortized O(1) per element.
 #[inline]
-pub fn append<T: IntoIterator<Item = U> + FromIterator<U>, U>(i: T, elem: U) -> T {
-    i.into_iter().chain(once(elem)).collect()
+pub fn append<T: Extend<U>, U>(mut i: T, elem: U) -> T {
+    i.extend(once(elem));
+    i
 }
 
 #[inline]
@@ -27,3 +30,35 @@ pub fn extend<
 pub fn advance(i: usize) -> usize {
     i + 1
 }
+
+/// One step of a trampolined loop: either recur with the next state or finish.
+pub enum Bounce<S, R> {
+    Recur(S),
+    Done(R),
+}
+
+/// Runs `step` from `state` until it is done. Lets an `aux` helper keep its
+/// tail-recursive shape while running in constant stack depth.
+pub fn trampoline<S, R>(state: S, mut step: impl FnMut(S) -> Bounce<S, R>) -> R {
+    let mut state = state;
+    loop {
+        match step(state) {
+            Bounce::Recur(next) => state = next,
+            Bounce::Done(result) => return result,
+        }
+    }
+}
+
+/// `trampoline` for steps that can fail, stopping at the first error.
+pub fn try_trampoline<S, R, E>(
+    state: S,
+    mut step: impl FnMut(S) -> Result<Bounce<S, R>, E>,
+) -> Result<R, E> {
+    let mut state = state;
+    loop {
+        match step(state)? {
+            Bounce::Recur(next) => state = next,
+            Bounce::Done(result) => return Ok(result),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+// Lets the code `#[derive(Lexer)]` generates, which names this crate, be
+// used inside it too.
+extern crate self as aoc_2023;
+
+pub mod days;
+pub mod diagnostics;
+pub mod functools;
+pub mod json;
+pub mod manifest;
+// Parsers take `&Vec<T>` token lists, and some end in `return aux(..)`.
+#[allow(clippy::ptr_arg, clippy::needless_return)]
+pub mod parsetools;
+pub mod read;
+pub mod scaffold;
+pub mod solution;
+pub mod stream;
+pub mod timing;
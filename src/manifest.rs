@@ -0,0 +1,142 @@
+//! The answers manifest `aoc verify` checks the days against. Each line
+//! gives a day, a part, the input file to run it on and the answer expected:
+//!
+//! ```text
+//! # day part input          answer
+//! 1     1    input/day1.txt 54632
+//! 1     2    input/day1.txt 54019
+//! ```
+//!
+//! Input paths are relative to the manifest, and `#` starts a comment.
+//! `answers.example.txt` is one for the puzzle examples, to copy from.
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use crate::days::Part;
+use crate::functools::*;
+use crate::parsetools::lextools as lt;
+use crate::parsetools::{self as pt, Index, ParseResult, Spanned};
+use crate::read::normalize;
+use crate::solution::{self, Error};
+
+/// Where `aoc verify` looks for the manifest when none is given.
+pub const DEFAULT_MANIFEST: &str = "./answers.txt";
+
+/// One expected answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub day: usize,
+    pub part: Part,
+    pub input: PathBuf,
+    pub answer: String,
+}
+
+/// Reads the manifest at `path`.
+pub fn read(path: &Path) -> solution::Result<Vec<Entry>> {
+    let source = fs::read_to_string(path).map_err(|err| {
+        let message = match err.kind() {
+            ErrorKind::NotFound => format!(
+                "answers manifest {} not found; pass --answers <path>, or copy answers.example.txt",
+                path.display()
+            ),
+            _ => format!("can't read {}: {err}", path.display()),
+        };
+        io::Error::new(err.kind(), message)
+    })?;
+    let dir = path.parent().unwrap_or(Path::new(""));
+    parse(&normalize(source), dir)
+}
+
+/// Parses a manifest whose input paths are relative to `dir`. Every bad line
+/// is reported, not just the first.
+pub fn parse(source: &str, dir: &Path) -> solution::Result<Vec<Entry>> {
+    let (tokens, lex_errors) = lt::lex_recovering(source, step);
+    let (lines, parse_errors) = pt::transform_recovering(
+        tokens,
+        |token| pt::is_token(&Token::Newline, &token.node),
+        parse_line,
+    );
+    let errors = pt::merge_errors(lex_errors, parse_errors);
+    if !errors.is_empty() {
+        return Err(Error::input(source, errors));
+    }
+
+    Ok(lines
+        .into_iter()
+        .filter_map(|line| line.node)
+        .map(|entry| Entry {
+            input: dir.join(entry.input),
+            ..entry
+        })
+        .collect())
+}
+
+#[derive(Debug)]
+enum Token<'a> {
+    /// Anything up to the next space, since paths and answers can hold most
+    /// characters.
+    Word(&'a str),
+    Newline,
+}
+
+fn step(input: &str, pos: Index) -> ParseResult<(Index, Token<'_>)> {
+    let pos = lt::skip_whitespace(input, pos)?;
+    match lt::peek(input, pos)? {
+        '\n' => Ok((advance(pos), Token::Newline)),
+        // A comment runs up to, but not including, the newline.
+        '#' => {
+            let (end, _) = lt::read_sequence(input, pos, |c| c != '\n', |_, _| Ok(()))?;
+            step(input, end)
+        }
+        _ => lt::read_sequence(
+            input,
+            pos,
+            |c| c != ' ' && c != '\n',
+            |_, word| Ok(Token::Word(word)),
+        ),
+    }
+}
+
+/// An entry, or `None` for a blank line.
+fn parse_line(tokens: &Vec<Spanned<Token>>, pos: Index) -> ParseResult<(Index, Option<Entry>)> {
+    if let Some(pos) = pt::is_token_at(tokens, pos, &Token::Newline) {
+        return Ok((pos, None));
+    }
+
+    let (next, day) = parse_word(tokens, pos, "a day")?;
+    let day = pt::parse_integer(pos, day)?;
+    let pos = next;
+
+    let (next, part) = parse_word(tokens, pos, "a part")?;
+    let part = match part {
+        "1" => Part::One,
+        "2" => Part::Two,
+        _ => return Err(pt::unexpected(tokens, pos, "part 1 or 2")),
+    };
+    let pos = next;
+
+    let (pos, input) = parse_word(tokens, pos, "an input file")?;
+    let (pos, answer) = parse_word(tokens, pos, "an answer")?;
+    let pos = pt::expect_token_at(tokens, pos, Token::Newline)?;
+
+    let entry = Entry {
+        day,
+        part,
+        input: PathBuf::from(input),
+        answer: answer.to_string(),
+    };
+    Ok((pos, Some(entry)))
+}
+
+fn parse_word<'a>(
+    tokens: &Vec<Spanned<Token<'a>>>,
+    pos: Index,
+    expected: &str,
+) -> ParseResult<(Index, &'a str)> {
+    match pt::token_at(tokens, pos) {
+        Some(Token::Word(word)) => Ok((advance(pos), word)),
+        _ => Err(pt::unexpected(tokens, pos, expected)),
+    }
+}
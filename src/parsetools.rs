@@ -1,151 +1,902 @@
+use std::any;
+use std::error;
 use std::fmt;
 use std::mem;
+use std::num::{IntErrorKind, ParseIntError};
+use std::str::FromStr;
 
-#[path = "./functools.rs"]
-mod functools;
-use functools::*;
+use crate::functools::*;
 
 pub type Index = usize;
 
-/// Works like a map
-pub fn transform<T, U>(input: Vec<T>, map: fn(&Vec<T>, Index) -> (Index, U)) -> Vec<U> {
-    return aux(&input, map, 0, Vec::new());
+pub type ParseResult<T> = Result<T, ParseError>;
 
-    fn aux<T, U>(
+/// Result of a whole lexing or parsing pass, with the error pinned to the source.
+pub type SpannedResult<T> = Result<T, Spanned<ParseError>>;
+
+/// Result of a pass that carries on past errors: as much output as could be
+/// made, and every error met along the way.
+pub type Recovered<T> = (T, Vec<Spanned<ParseError>>);
+
+/// A point in the source text. Lines and columns count from 1, `offset` is
+/// the byte offset from the start of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
+
+impl Location {
+    /// The location just after `c`, if `c` starts at `self`.
+    pub fn advance(self, c: char) -> Location {
+        if c == '\n' {
+            Location {
+                line: self.line + 1,
+                column: 1,
+                offset: self.offset + 1,
+            }
+        } else {
+            Location {
+                line: self.line,
+                column: self.column + 1,
+                offset: self.offset + c.len_utf8(),
+            }
+        }
+    }
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Location {
+            line: 1,
+            column: 1,
+            offset: 0,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// The stretch of source between `start` (inclusive) and `end` (exclusive).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Span {
+    pub start: Location,
+    pub end: Location,
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// A token, AST node or error together with where it came from.
+#[derive(Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(node: T, span: Span) -> Self {
+        Spanned { node, span }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Spanned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.node.fmt(f)?;
+        write!(f, " @ {}", self.span)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Spanned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.span.start, self.node)
+    }
+}
+
+impl<T: error::Error> error::Error for Spanned<T> {}
+
+/// Anything `transform` can walk over while keeping track of where it is.
+pub trait Locate {
+    /// Where this item starts, given the location right after the previous one.
+    fn start(&self, location: Location) -> Location {
+        location
+    }
+
+    /// The location right after this item, given where it starts.
+    fn end(&self, start: Location) -> Location;
+}
+
+impl<T> Locate for Spanned<T> {
+    fn start(&self, _: Location) -> Location {
+        self.span.start
+    }
+
+    fn end(&self, _: Location) -> Location {
+        self.span.end
+    }
+}
+
+/// Measures the span of `input[start..end]`, given the location `start` is at.
+pub fn measure<T: Locate>(input: &Vec<T>, start: Index, end: Index, location: Location) -> Span {
+    return trampoline((start, location, None), |(pos, location, span_start)| {
+        aux(input, pos, end, location, span_start)
+    });
+
+    fn aux<T: Locate>(
+        input: &Vec<T>,
+        pos: Index,
+        end: Index,
+        location: Location,
+        span_start: Option<Location>,
+    ) -> Bounce<(Index, Location, Option<Location>), Span> {
+        if pos >= end || is_end(input, pos) {
+            return Bounce::Done(Span {
+                start: span_start.unwrap_or(location),
+                end: location,
+            });
+        }
+
+        let item = &input[pos];
+        let start = item.start(location);
+        let span_start = span_start.or(Some(start));
+
+        Bounce::Recur((advance(pos), item.end(start), span_start))
+    }
+}
+
+/// Pins an error to the source by walking `input` up to the error's position.
+pub fn locate<T: Locate>(input: &Vec<T>, error: ParseError) -> Spanned<ParseError> {
+    locate_from(input, 0, Location::default(), error)
+}
+
+/// Like `locate`, but walks from `start`, known to be at `location`, rather
+/// than from the beginning, so locating an error costs no more than the item
+/// it was found in.
+pub fn locate_from<T: Locate>(
+    input: &Vec<T>,
+    start: Index,
+    location: Location,
+    error: ParseError,
+) -> Spanned<ParseError> {
+    let pos = error.pos();
+    let before = measure(input, start, pos, location).end;
+    let span = match input.get(pos) {
+        Some(item) => {
+            let start = item.start(before);
+            Span {
+                start,
+                end: item.end(start),
+            }
+        }
+        None => Span {
+            start: before,
+            end: before,
+        },
+    };
+    Spanned::new(error, span)
+}
+
+/// Errors shared by every day's lexer and parser. `pos` is an index into
+/// whatever is being consumed: chars for a lexer, tokens for a parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    UnexpectedToken {
+        pos: Index,
+        expected: String,
+        found: String,
+    },
+    UnknownCharacter {
+        pos: Index,
+        c: char,
+    },
+    UnexpectedEof {
+        pos: Index,
+        expected: String,
+    },
+    UnknownIdentifier {
+        pos: Index,
+        ident: String,
+        expected: String,
+    },
+    BadNumber {
+        pos: Index,
+        text: String,
+    },
+    NumberOutOfRange {
+        pos: Index,
+        text: String,
+        ty: String,
+    },
+}
+
+impl ParseError {
+    pub fn pos(&self) -> Index {
+        use ParseError::*;
+        match self {
+            UnexpectedToken { pos, .. }
+            | UnknownCharacter { pos, .. }
+            | UnexpectedEof { pos, .. }
+            | UnknownIdentifier { pos, .. }
+            | BadNumber { pos, .. }
+            | NumberOutOfRange { pos, .. } => *pos,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use ParseError::*;
+        match self {
+            UnexpectedToken {
+                expected, found, ..
+            } => write!(f, "expected {expected}, found {found}"),
+            UnknownCharacter { c, .. } => write!(f, "unknown character {c:?}"),
+            UnexpectedEof { expected, .. } => write!(f, "expected {expected}, found end of input"),
+            UnknownIdentifier {
+                ident, expected, ..
+            } => write!(f, "unknown identifier {ident:?}, expected {expected}"),
+            BadNumber { text, .. } => write!(f, "invalid number {text:?}"),
+            NumberOutOfRange { text, ty, .. } => write!(f, "number {text} doesn't fit in {ty}"),
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Parses `text`, found at `pos`, as an integer of type `N`, telling a number
+/// too large for `N` apart from one that is malformed.
+pub fn parse_integer<N: FromStr<Err = ParseIntError>>(pos: Index, text: &str) -> ParseResult<N> {
+    text.parse().map_err(|err: ParseIntError| match err.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => ParseError::NumberOutOfRange {
+            pos,
+            text: text.to_string(),
+            ty: any::type_name::<N>().to_string(),
+        },
+        _ => ParseError::BadNumber {
+            pos,
+            text: text.to_string(),
+        },
+    })
+}
+
+/// Works like a map, recording the span each output was produced from. `map`
+/// consumes `input` from `pos` onwards, producing the next position and a value.
+pub fn transform<T: Locate, U>(
+    input: Vec<T>,
+    mut map: impl FnMut(&Vec<T>, Index) -> ParseResult<(Index, U)>,
+) -> SpannedResult<Vec<Spanned<U>>> {
+    return try_trampoline(
+        (0, Location::default(), Vec::new()),
+        |(pos, location, outputs)| aux(&input, &mut map, pos, location, outputs),
+    );
+
+    type State<U> = (Index, Location, Vec<Spanned<U>>);
+
+    fn aux<T: Locate, U>(
         input: &Vec<T>,
-        map: fn(&Vec<T>, usize) -> (usize, U),
+        map: &mut impl FnMut(&Vec<T>, Index) -> ParseResult<(Index, U)>,
         pos: Index,
-        outputs: Vec<U>,
-    ) -> Vec<U> {
+        location: Location,
+        outputs: Vec<Spanned<U>>,
+    ) -> SpannedResult<Bounce<State<U>, Vec<Spanned<U>>>> {
         if is_end(input, pos) {
-            return outputs;
+            return Ok(Bounce::Done(outputs));
         }
 
-        let (pos, output) = map(input, pos);
+        let (end, output) =
+            map(input, pos).map_err(|error| locate_from(input, pos, location, error))?;
+        let span = measure(input, pos, end, location);
+
+        Ok(Bounce::Recur((
+            end,
+            span.end,
+            append(outputs, Spanned::new(output, span)),
+        )))
+    }
+}
+
+/// Like `transform`, but keeps going after an error: the error is recorded,
+/// and `map` resumes just past the next item `is_sync` accepts, so every
+/// record that fails to parse gets its own error.
+pub fn transform_recovering<T: Locate, U>(
+    input: Vec<T>,
+    is_sync: impl Fn(&T) -> bool,
+    mut map: impl FnMut(&Vec<T>, Index) -> ParseResult<(Index, U)>,
+) -> Recovered<Vec<Spanned<U>>> {
+    return trampoline(
+        (0, Location::default(), (Vec::new(), Vec::new())),
+        |(pos, location, recovered)| aux(&input, &is_sync, &mut map, pos, location, recovered),
+    );
+
+    type State<U> = (Index, Location, Recovered<Vec<Spanned<U>>>);
+
+    fn aux<T: Locate, U>(
+        input: &Vec<T>,
+        is_sync: &impl Fn(&T) -> bool,
+        map: &mut impl FnMut(&Vec<T>, Index) -> ParseResult<(Index, U)>,
+        pos: Index,
+        location: Location,
+        (outputs, errors): Recovered<Vec<Spanned<U>>>,
+    ) -> Bounce<State<U>, Recovered<Vec<Spanned<U>>>> {
+        if is_end(input, pos) {
+            return Bounce::Done((outputs, errors));
+        }
 
-        aux(input, map, pos, append(outputs, output))
+        match map(input, pos) {
+            Ok((end, output)) => {
+                let span = measure(input, pos, end, location);
+                let outputs = append(outputs, Spanned::new(output, span));
+                Bounce::Recur((end, span.end, (outputs, errors)))
+            }
+            Err(error) => {
+                let resume = input
+                    .iter()
+                    .enumerate()
+                    .skip(error.pos().max(pos))
+                    .find(|(_, item)| is_sync(item))
+                    .map_or(input.len(), |(sync, _)| advance(sync));
+                let errors = append(errors, locate_from(input, pos, location, error));
+                let location = measure(input, pos, resume, location).end;
+                Bounce::Recur((resume, location, (outputs, errors)))
+            }
+        }
     }
 }
 
+/// Puts lexing and parsing errors in source order, in one pass over each,
+/// since `lex_recovering` and `transform_recovering` both report theirs in
+/// source order. A parsing error on a line the lexer already complained about
+/// is usually a knock-on effect of the tokens the lexer skipped, so it is
+/// left out.
+pub fn merge_errors(
+    lexing: Vec<Spanned<ParseError>>,
+    parsing: Vec<Spanned<ParseError>>,
+) -> Vec<Spanned<ParseError>> {
+    let mut lexed_lines = lexing.iter().map(|error| error.span.start.line).peekable();
+    let parsing: Vec<_> = parsing
+        .into_iter()
+        .filter(|error| {
+            let line = error.span.start.line;
+            while lexed_lines.next_if(|&lexed| lexed < line).is_some() {}
+            lexed_lines.peek() != Some(&line)
+        })
+        .collect();
+
+    let mut lexing = lexing.into_iter().peekable();
+    let mut parsing = parsing.into_iter().peekable();
+    std::iter::from_fn(|| match (lexing.peek(), parsing.peek()) {
+        (Some(lexed), Some(parsed)) if parsed.span.start < lexed.span.start => parsing.next(),
+        (Some(_), _) => lexing.next(),
+        (None, _) => parsing.next(),
+    })
+    .collect()
+}
+
+/// Lexing helpers over a borrowed `&str`. Positions are byte offsets into the
+/// input, so tokens can hold slices of it instead of copies.
 pub mod lextools {
     use super::*;
 
-    pub fn skip_whitespace(input: &Vec<char>, pos: Index) -> Index {
-        let (pos, _) = read_sequence(input, pos, |c| c == ' ', |_| ());
-        pos
+    pub use aoc_2023_derive::Lexer;
+
+    /// A token type that knows how to lex a whole input, usually written with
+    /// `#[derive(Lexer)]`.
+    pub trait Lexer<'a>: Sized {
+        /// Reads the token at `pos`, returning the position after it.
+        fn step(input: &'a str, pos: Index) -> ParseResult<(Index, Self)>;
+
+        fn lex(input: &'a str) -> SpannedResult<Vec<Spanned<Self>>> {
+            lex(input, Self::step)
+        }
+
+        fn lex_recovering(input: &'a str) -> Recovered<Vec<Spanned<Self>>> {
+            lex_recovering(input, Self::step)
+        }
     }
 
-    pub fn read_number(input: &Vec<char>, pos: Index) -> (Index, usize) {
-        read_sequence(
-            input,
-            pos,
-            |c| c.is_digit(10),
-            |chars| chars.iter().collect::<String>().parse().unwrap(),
-        )
+    /// Works like `transform` over the characters of `input`, recording the
+    /// span each token was read from. `step` consumes `input` from `pos`
+    /// onwards, producing the next position and a token.
+    pub fn lex<'a, U>(
+        input: &'a str,
+        mut step: impl FnMut(&'a str, Index) -> ParseResult<(Index, U)>,
+    ) -> SpannedResult<Vec<Spanned<U>>> {
+        return try_trampoline(
+            (0, Location::default(), Vec::new()),
+            |(pos, location, outputs)| aux(input, &mut step, pos, location, outputs),
+        );
+
+        type State<U> = (Index, Location, Vec<Spanned<U>>);
+
+        fn aux<'a, U>(
+            input: &'a str,
+            step: &mut impl FnMut(&'a str, Index) -> ParseResult<(Index, U)>,
+            pos: Index,
+            location: Location,
+            outputs: Vec<Spanned<U>>,
+        ) -> SpannedResult<Bounce<State<U>, Vec<Spanned<U>>>> {
+            if is_end(input.as_bytes(), pos) {
+                return Ok(Bounce::Done(outputs));
+            }
+
+            let (end, output) =
+                step(input, pos).map_err(|error| locate_from(input, pos, location, error))?;
+            let span = measure(input, pos, end, location);
+
+            Ok(Bounce::Recur((
+                end,
+                span.end,
+                append(outputs, Spanned::new(output, span)),
+            )))
+        }
     }
 
-    pub fn read_identifier(input: &Vec<char>, pos: Index) -> (Index, String) {
-        read_sequence(
-            input,
-            pos,
-            |c| c.is_alphabetic(),
-            |chars| chars.iter().collect(),
-        )
+    /// Like `lex`, but keeps going after an error: the error is recorded and
+    /// the rest of its line skipped, so the newline ending it is still lexed.
+    pub fn lex_recovering<'a, U>(
+        input: &'a str,
+        mut step: impl FnMut(&'a str, Index) -> ParseResult<(Index, U)>,
+    ) -> Recovered<Vec<Spanned<U>>> {
+        return trampoline(
+            (0, Location::default(), (Vec::new(), Vec::new())),
+            |(pos, location, recovered)| aux(input, &mut step, pos, location, recovered),
+        );
+
+        type State<U> = (Index, Location, Recovered<Vec<Spanned<U>>>);
+
+        fn aux<'a, U>(
+            input: &'a str,
+            step: &mut impl FnMut(&'a str, Index) -> ParseResult<(Index, U)>,
+            pos: Index,
+            location: Location,
+            (outputs, errors): Recovered<Vec<Spanned<U>>>,
+        ) -> Bounce<State<U>, Recovered<Vec<Spanned<U>>>> {
+            if is_end(input.as_bytes(), pos) {
+                return Bounce::Done((outputs, errors));
+            }
+
+            match step(input, pos) {
+                Ok((end, output)) => {
+                    let span = measure(input, pos, end, location);
+                    let outputs = append(outputs, Spanned::new(output, span));
+                    Bounce::Recur((end, span.end, (outputs, errors)))
+                }
+                Err(error) => {
+                    let from = error.pos().clamp(pos, input.len());
+                    // A newline the lexer can't read is skipped like any
+                    // other character, so there is always progress.
+                    let resume = match input[from..].find('\n') {
+                        Some(0) if from == pos => advance(pos),
+                        Some(len) => from + len,
+                        None => input.len(),
+                    };
+                    let errors = append(errors, locate_from(input, pos, location, error));
+                    let location = measure(input, pos, resume, location).end;
+                    Bounce::Recur((resume, location, (outputs, errors)))
+                }
+            }
+        }
+    }
+
+    /// Measures the span of `input[start..end]`, given the location `start` is
+    /// at. Leading spaces are left out, so a token's span starts at the token
+    /// itself rather than at the whitespace before it.
+    pub fn measure(input: &str, start: Index, end: Index, location: Location) -> Span {
+        let text = &input[start..end];
+        let (end, start) =
+            text.char_indices()
+                .fold((location, None), |(location, span_start), (pos, c)| {
+                    let span_start = match span_start {
+                        None if c != ' ' || pos + c.len_utf8() == text.len() => Some(location),
+                        span_start => span_start,
+                    };
+                    (location.advance(c), span_start)
+                });
+
+        Span {
+            start: start.unwrap_or(end),
+            end,
+        }
+    }
+
+    /// Pins a lexing error to the source, covering the character it points
+    /// at, or the whole text of an unknown identifier or bad number.
+    pub fn locate(input: &str, error: ParseError) -> Spanned<ParseError> {
+        locate_from(input, 0, Location::default(), error)
+    }
+
+    /// Like `locate`, but measures from `start`, known to be at `location`,
+    /// rather than from the beginning of `input`.
+    pub fn locate_from(
+        input: &str,
+        start: Index,
+        location: Location,
+        error: ParseError,
+    ) -> Spanned<ParseError> {
+        let pos = error.pos().clamp(start, input.len());
+        let start = measure(input, start, pos, location).end;
+        let end = match &error {
+            ParseError::UnknownIdentifier { ident: text, .. }
+            | ParseError::BadNumber { text, .. }
+            | ParseError::NumberOutOfRange { text, .. } => {
+                measure(input, pos, pos + text.len(), start).end
+            }
+            _ => input[pos..]
+                .chars()
+                .next()
+                .map_or(start, |c| start.advance(c)),
+        };
+        Spanned::new(error, Span { start, end })
+    }
+
+    /// Returns the character at `pos`, or an error if the input has run out.
+    pub fn peek(input: &str, pos: Index) -> ParseResult<char> {
+        input
+            .get(pos..)
+            .and_then(|rest| rest.chars().next())
+            .ok_or(ParseError::UnexpectedEof {
+                pos,
+                expected: "a character".to_string(),
+            })
     }
 
-    pub fn read_sequence<T>(
-        input: &Vec<char>,
+    pub fn skip_whitespace(input: &str, pos: Index) -> ParseResult<Index> {
+        let (pos, _) = read_sequence(input, pos, |c| c == ' ', |_, _| Ok(()))?;
+        Ok(pos)
+    }
+
+    /// Reads a run of digits as an integer of type `N`, e.g. `usize` or `u128`.
+    pub fn read_number<N: FromStr<Err = ParseIntError>>(
+        input: &str,
         pos: Index,
-        predicate: fn(char) -> bool,
-        map: fn(Vec<char>) -> T,
-    ) -> (Index, T) {
-        return aux(input, pos, predicate, map, Vec::new());
+    ) -> ParseResult<(Index, N)> {
+        read_sequence(input, pos, |c| c.is_ascii_digit(), parse_integer)
+    }
 
-        fn aux<T>(
-            input: &Vec<char>,
-            pos: Index,
-            predicate: fn(char) -> bool,
-            map: fn(Vec<char>) -> T,
-            chars: Vec<char>,
-        ) -> (Index, T) {
-            let c = input[pos];
-            if is_end(input, pos) || !predicate(c) {
-                return (pos, map(chars));
+    /// Reads an integer with an optional leading `-` or `+`, e.g. as an `i64`
+    /// or `i128`.
+    pub fn read_signed<N: FromStr<Err = ParseIntError>>(
+        input: &str,
+        pos: Index,
+    ) -> ParseResult<(Index, N)> {
+        let digits = match peek(input, pos)? {
+            '-' | '+' => advance(pos),
+            _ => pos,
+        };
+        let (end, _) = read_sequence(input, digits, |c| c.is_ascii_digit(), |_, _| Ok(()))?;
+        Ok((end, parse_integer(pos, &input[pos..end])?))
+    }
+
+    /// Whether a signed number starts at `pos`: a digit, or a sign followed
+    /// by one.
+    pub fn is_signed_number(input: &str, pos: Index) -> bool {
+        let mut chars = input[pos..].chars();
+        match chars.next() {
+            Some('-' | '+') => chars.next().is_some_and(|c| c.is_ascii_digit()),
+            Some(c) => c.is_ascii_digit(),
+            None => false,
+        }
+    }
+
+    pub fn read_identifier(input: &str, pos: Index) -> ParseResult<(Index, &str)> {
+        read_sequence(input, pos, |c| c.is_alphabetic(), |_, text| Ok(text))
+    }
+
+    /// A keyword and the constructor of the token it stands for.
+    pub type Keyword<'a, T> = (&'a str, fn() -> T);
+
+    /// A lexer's vocabulary: its keywords, and optionally what to make of any
+    /// other identifier.
+    pub struct Keywords<'a, T> {
+        table: &'a [Keyword<'a, T>],
+        fallback: Option<fn(&'a str) -> T>,
+    }
+
+    impl<'a, T> Keywords<'a, T> {
+        pub fn new(table: &'a [Keyword<'a, T>]) -> Self {
+            Keywords {
+                table,
+                fallback: None,
             }
-            aux(input, advance(pos), predicate, map, append(chars, c))
+        }
+
+        /// Identifiers missing from the table become `fallback(ident)`
+        /// instead of an error.
+        pub fn or_else(self, fallback: fn(&'a str) -> T) -> Self {
+            Keywords {
+                fallback: Some(fallback),
+                ..self
+            }
+        }
+
+        /// The keywords as a readable list, e.g. `"to" or "map"`.
+        fn expected(&self) -> String {
+            let keywords: Vec<String> = self.table.iter().map(|(k, _)| format!("{k:?}")).collect();
+            match keywords.split_last() {
+                Some((last, [])) => last.clone(),
+                Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+                None => "no identifiers".to_string(),
+            }
+        }
+    }
+
+    /// Reads an identifier and looks it up in `keywords`.
+    pub fn read_keyword<'a, T>(
+        input: &'a str,
+        pos: Index,
+        keywords: &Keywords<'a, T>,
+    ) -> ParseResult<(Index, T)> {
+        let (end, ident) = read_identifier(input, pos)?;
+        let token = keywords
+            .table
+            .iter()
+            .find(|(keyword, _)| *keyword == ident)
+            .map(|(_, token)| token())
+            .or_else(|| keywords.fallback.map(|fallback| fallback(ident)));
+
+        match token {
+            Some(token) => Ok((end, token)),
+            None => Err(ParseError::UnknownIdentifier {
+                pos,
+                ident: ident.to_string(),
+                expected: keywords.expected(),
+            }),
         }
     }
+
+    /// Reads the longest run of characters matching `predicate`, then hands
+    /// the run and its starting offset to `map`.
+    pub fn read_sequence<'a, T>(
+        input: &'a str,
+        pos: Index,
+        predicate: impl Fn(char) -> bool,
+        map: impl FnOnce(Index, &'a str) -> ParseResult<T>,
+    ) -> ParseResult<(Index, T)> {
+        let end = input[pos..]
+            .find(|c| !predicate(c))
+            .map_or(input.len(), |len| pos + len);
+        return Ok((end, map(pos, &input[pos..end])?));
+    }
 }
 
 pub fn is_token<T>(expected_token: &T, token: &T) -> bool {
     mem::discriminant(expected_token) == mem::discriminant(token)
 }
 
-pub fn is_token_at<T: fmt::Debug>(input: &Vec<T>, pos: Index, expected_token: &T) -> Option<Index> {
-    let token = &input[pos];
-    if is_token(expected_token, token) {
-        Some(advance(pos))
-    } else {
-        None
+/// The token at `pos` without its span, if there is one.
+pub fn token_at<T>(input: &Vec<Spanned<T>>, pos: Index) -> Option<&T> {
+    input.get(pos).map(|token| &token.node)
+}
+
+pub fn is_token_at<T: fmt::Debug>(
+    input: &Vec<Spanned<T>>,
+    pos: Index,
+    expected_token: &T,
+) -> Option<Index> {
+    match input.get(pos) {
+        Some(token) if is_token(expected_token, &token.node) => Some(advance(pos)),
+        _ => None,
     }
 }
 
-pub fn parse_numbers<T>(
-    tokens: &Vec<T>,
+pub fn parse_numbers<T, N: Copy>(
+    tokens: &Vec<Spanned<T>>,
     pos: Index,
     number_token: T,
-    extract: fn(&T) -> &usize,
-) -> (Index, Vec<usize>) {
-    return aux(tokens, pos, number_token, extract, Vec::new());
+    extract: impl Fn(&T) -> &N,
+) -> (Index, Vec<N>) {
+    return trampoline((pos, Vec::new()), |(pos, numbers)| {
+        aux(tokens, pos, &number_token, &extract, numbers)
+    });
 
-    fn aux<T>(
-        tokens: &Vec<T>,
+    fn aux<T, N: Copy>(
+        tokens: &Vec<Spanned<T>>,
         pos: Index,
-        number_token: T,
-        extract: fn(&T) -> &usize,
-        numbers: Vec<usize>,
-    ) -> (Index, Vec<usize>) {
-        let token = &tokens[pos];
-
-        if is_end(tokens, pos) || !is_token(&number_token, token) {
-            return (pos, numbers);
+        number_token: &T,
+        extract: &impl Fn(&T) -> &N,
+        numbers: Vec<N>,
+    ) -> Bounce<(Index, Vec<N>), (Index, Vec<N>)> {
+        if is_end(tokens, pos) || !is_token(number_token, &tokens[pos].node) {
+            return Bounce::Done((pos, numbers));
         }
 
-        let number = extract(token);
+        let number = extract(&tokens[pos].node);
 
-        aux(
-            tokens,
-            advance(pos),
-            number_token,
-            extract,
-            append(numbers, *number),
-        )
+        Bounce::Recur((advance(pos), append(numbers, *number)))
     }
 }
 
-pub fn expect_token<T: fmt::Debug>(expected_token: &T, token: &T) {
-    if !is_token(expected_token, &token) {
-        panic!("expected token {expected_token:?}, found {token:?}");
+pub fn expect_token_at<T: fmt::Debug>(
+    input: &Vec<Spanned<T>>,
+    pos: Index,
+    expected_token: T,
+) -> ParseResult<Index> {
+    if let Some(pos) = is_token_at(input, pos, &expected_token) {
+        Ok(pos)
+    } else {
+        Err(unexpected(input, pos, &format!("{expected_token:?}")))
     }
 }
 
-pub fn expect_token_at<T: fmt::Debug>(input: &Vec<T>, pos: Index, expected_token: T) -> Index {
-    if let Some(pos) = is_token_at(input, pos, &expected_token) {
-        pos
-    } else {
-        panic!("expected token {expected_token:?}, found {:?}", &input[pos]);
+/// Builds the error for finding something other than `expected` at `pos`.
+pub fn unexpected<T: fmt::Debug>(
+    input: &Vec<Spanned<T>>,
+    pos: Index,
+    expected: &str,
+) -> ParseError {
+    let expected = expected.to_string();
+    match input.get(pos) {
+        Some(token) => ParseError::UnexpectedToken {
+            pos,
+            expected,
+            found: format!("{:?}", token.node),
+        },
+        None => ParseError::UnexpectedEof { pos, expected },
+    }
+}
+
+/// Parser combinators over a token stream. A parser consumes `tokens` from
+/// `pos` and returns the next position and a value, so plain functions like
+/// `fn(&Vec<T>, Index) -> ParseResult<(Index, O)>` are parsers too.
+///
+/// A parser that fails right where it started is taken not to match: `many`
+/// and `sep_by` stop there, `optional` gives `None` and `alt` tries the next
+/// option. A parser that fails after consuming tokens has found a real error,
+/// which is always passed on.
+pub mod combinators {
+    use super::*;
+
+    pub trait Parser<T, O>: Fn(&Vec<T>, Index) -> ParseResult<(Index, O)> {}
+
+    impl<T, O, P: Fn(&Vec<T>, Index) -> ParseResult<(Index, O)>> Parser<T, O> for P {}
+
+    /// `Ok(None)` if `result` failed without consuming anything from `pos`.
+    fn matched<O>(result: ParseResult<(Index, O)>, pos: Index) -> ParseResult<Option<(Index, O)>> {
+        match result {
+            Ok(output) => Ok(Some(output)),
+            Err(error) if error.pos() == pos => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Matches a single token of the same variant as `expected`.
+    pub fn token<T: fmt::Debug>(expected: T) -> impl Parser<Spanned<T>, ()> {
+        move |tokens, pos| match is_token_at(tokens, pos, &expected) {
+            Some(pos) => Ok((pos, ())),
+            None => Err(unexpected(tokens, pos, &format!("{expected:?}"))),
+        }
+    }
+
+    /// Zero or more `parser`s in a row.
+    pub fn many<T, O>(parser: impl Parser<T, O>) -> impl Parser<T, Vec<O>> {
+        move |tokens, pos| {
+            try_trampoline((pos, Vec::new()), |(pos, outputs)| {
+                match matched(parser(tokens, pos), pos)? {
+                    Some((next, output)) if next > pos => {
+                        Ok(Bounce::Recur((next, append(outputs, output))))
+                    }
+                    _ => Ok(Bounce::Done((pos, outputs))),
+                }
+            })
+        }
+    }
+
+    /// One or more `parser`s in a row.
+    pub fn many1<T, O>(parser: impl Parser<T, O>) -> impl Parser<T, Vec<O>> {
+        move |tokens, pos| {
+            let (pos, first) = parser(tokens, pos)?;
+            let (pos, rest) = many(&parser)(tokens, pos)?;
+            Ok((pos, extend(vec![first], rest)))
+        }
+    }
+
+    /// Zero or more `parser`s with a `separator` between each.
+    pub fn sep_by<T, O, S>(
+        parser: impl Parser<T, O>,
+        separator: impl Parser<T, S>,
+    ) -> impl Parser<T, Vec<O>> {
+        move |tokens, pos| {
+            let Some((pos, first)) = matched(parser(tokens, pos), pos)? else {
+                return Ok((pos, Vec::new()));
+            };
+            let (pos, rest) = many(sequence(&separator, &parser))(tokens, pos)?;
+            let rest: Vec<O> = rest.into_iter().map(|(_, output)| output).collect();
+            Ok((pos, extend(vec![first], rest)))
+        }
+    }
+
+    /// `parser` if it matches, `None` otherwise.
+    pub fn optional<T, O>(parser: impl Parser<T, O>) -> impl Parser<T, Option<O>> {
+        move |tokens, pos| match matched(parser(tokens, pos), pos)? {
+            Some((pos, output)) => Ok((pos, Some(output))),
+            None => Ok((pos, None)),
+        }
+    }
+
+    /// `first` if it matches, `second` otherwise. If neither matches, the
+    /// error lists what both expected.
+    pub fn alt<T, O>(first: impl Parser<T, O>, second: impl Parser<T, O>) -> impl Parser<T, O> {
+        move |tokens, pos| match first(tokens, pos) {
+            Err(error) if error.pos() == pos => {
+                second(tokens, pos).map_err(|other| either(error, other))
+            }
+            result => result,
+        }
+    }
+
+    fn either(first: ParseError, second: ParseError) -> ParseError {
+        use ParseError::*;
+        match (first, second) {
+            (
+                UnexpectedToken {
+                    pos, expected: a, ..
+                },
+                UnexpectedToken {
+                    pos: other,
+                    expected: b,
+                    found,
+                },
+            ) if pos == other => UnexpectedToken {
+                pos,
+                expected: format!("{a} or {b}"),
+                found,
+            },
+            (
+                UnexpectedEof { pos, expected: a },
+                UnexpectedEof {
+                    pos: other,
+                    expected: b,
+                },
+            ) if pos == other => UnexpectedEof {
+                pos,
+                expected: format!("{a} or {b}"),
+            },
+            (_, second) => second,
+        }
+    }
+
+    /// `first` followed by `second`.
+    pub fn sequence<T, A, B>(
+        first: impl Parser<T, A>,
+        second: impl Parser<T, B>,
+    ) -> impl Parser<T, (A, B)> {
+        move |tokens, pos| {
+            let (pos, a) = first(tokens, pos)?;
+            let (pos, b) = second(tokens, pos)?;
+            Ok((pos, (a, b)))
+        }
+    }
+
+    /// `parser` followed by `end`, keeping only what `parser` produced.
+    pub fn terminated_by<T, O, E>(
+        parser: impl Parser<T, O>,
+        end: impl Parser<T, E>,
+    ) -> impl Parser<T, O> {
+        move |tokens, pos| {
+            let (pos, output) = parser(tokens, pos)?;
+            let (pos, _) = end(tokens, pos)?;
+            Ok((pos, output))
+        }
     }
 }
 
 #[macro_export]
 macro_rules! tail_end {
     ($input:ident[$pos:ident], return $return:expr) => {
-        if crate::functools::is_end($input, $pos) {
+        if $crate::functools::is_end($input, $pos) {
             return $return;
         }
     };
     ($input:ident[$pos:ident], if $condition:expr, return $return:expr) => {
-        if $condition || crate::functools::is_end($input, $pos) {
+        if $condition || $crate::functools::is_end($input, $pos) {
             return $return;
         }
     };
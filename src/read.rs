@@ -1,5 +1,149 @@
-use std::{fs, path::Path};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
 
-pub fn read<P: AsRef<Path>>(path: P) -> std::io::Result<Vec<char>> {
-    fs::read(path).map(|bytes| bytes.into_iter().map(|i| i as char).collect())
+use crate::functools::append;
+
+/// Where inputs are looked for when no path is given, unless `AOC_INPUT_DIR`
+/// says otherwise.
+pub const DEFAULT_INPUT_DIR: &str = "./input";
+
+/// Where a puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Input {
+    /// An input named on the command line, where `-` stands for stdin.
+    pub fn from_arg(arg: &str) -> Input {
+        match arg {
+            "-" => Input::Stdin,
+            path => Input::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Day `number`'s input, `dayN.txt` in `$AOC_INPUT_DIR` or else in
+    /// `DEFAULT_INPUT_DIR`.
+    pub fn for_day(number: usize) -> Input {
+        let dir = env::var_os("AOC_INPUT_DIR").unwrap_or(DEFAULT_INPUT_DIR.into());
+        Input::File(Path::new(&dir).join(format!("day{number}.txt")))
+    }
+
+    /// Reads the whole input as UTF-8, like `read`.
+    pub fn read(&self) -> io::Result<String> {
+        self.bytes().and_then(from_utf8)
+    }
+
+    /// Reads the whole input as ASCII, like `read_ascii`.
+    pub fn read_ascii(&self) -> io::Result<String> {
+        self.bytes().and_then(from_ascii)
+    }
+
+    /// Opens the input for reading a bit at a time.
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Input::File(path) => {
+                let file = fs::File::open(path).map_err(|err| self.context(err))?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+            Input::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+
+    fn bytes(&self) -> io::Result<Vec<u8>> {
+        let bytes = match self {
+            Input::File(path) => fs::read(path),
+            Input::Stdin => {
+                let mut bytes = Vec::new();
+                io::stdin().read_to_end(&mut bytes).map(|_| bytes)
+            }
+        };
+        bytes.map_err(|err| self.context(err))
+    }
+
+    /// Says which input `err` came from, and for a missing file, how to
+    /// point at another one.
+    fn context(&self, err: io::Error) -> io::Error {
+        let message = match err.kind() {
+            ErrorKind::NotFound => {
+                format!("input file {self} not found; pass --input <path> or set AOC_INPUT_DIR")
+            }
+            _ => format!("can't read {self}: {err}"),
+        };
+        io::Error::new(err.kind(), message)
+    }
+}
+
+/// The path, or `<stdin>`, as diagnostics should name it.
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::File(path) => write!(f, "{}", path.display()),
+            Input::Stdin => write!(f, "<stdin>"),
+        }
+    }
+}
+
+/// Reads a UTF-8 file.
+pub fn read<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    Input::File(path.as_ref().to_path_buf()).read()
+}
+
+/// Reads a file that must be one byte per character, for puzzles whose grid
+/// positions are byte offsets. Anything outside ASCII is rejected rather than
+/// guessed at.
+pub fn read_ascii<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    Input::File(path.as_ref().to_path_buf()).read_ascii()
+}
+
+/// Reads everything left in `reader` as UTF-8, like `read`.
+pub fn read_all(mut reader: impl Read) -> io::Result<String> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    from_utf8(bytes)
+}
+
+/// Reads everything left in `reader` as ASCII, like `read_ascii`.
+pub fn read_all_ascii(mut reader: impl Read) -> io::Result<String> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    from_ascii(bytes)
+}
+
+fn from_utf8(bytes: Vec<u8>) -> io::Result<String> {
+    String::from_utf8(bytes).map_err(|err| {
+        let offset = err.utf8_error().valid_up_to();
+        invalid_data(format!("invalid UTF-8 at byte {offset}"))
+    })
+}
+
+fn from_ascii(bytes: Vec<u8>) -> io::Result<String> {
+    match bytes.iter().position(|byte| !byte.is_ascii()) {
+        Some(offset) => Err(invalid_data(format!("non-ASCII byte at {offset}"))),
+        None => Ok(bytes.into_iter().map(char::from).collect()),
+    }
+}
+
+/// Irons out the differences between how an input may have been saved, so
+/// lexers only ever see `\n` line endings, spaces and a final newline:
+/// CRLF and lone CR become LF, tabs become spaces, and a missing trailing
+/// newline is added.
+pub fn normalize(input: String) -> String {
+    let text = input
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\t', " ");
+
+    if text.is_empty() || text.ends_with('\n') {
+        text
+    } else {
+        append(text, '\n')
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
 }
@@ -0,0 +1,270 @@
+//! `aoc new`: a new day laid out like the others, with a lexer, a parser and
+//! an evaluator on top of `parsetools`, registered with the runner and given
+//! an example test to fill in.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use crate::functools::*;
+use crate::read::Input;
+
+/// The new day's module, with `__N__` standing for its number. It reads lines
+/// of numbers and sums them, so it runs as is until the real puzzle is in.
+/// Like the other days it takes `&Vec<T>`, so its declaration is registered
+/// under `MODULE_ATTRIBUTE`.
+const DAY_TEMPLATE: &str = r#"use std::fmt::Display;
+
+use crate::parsetools::lextools::Lexer;
+use crate::parsetools::{self as pt, ParseResult, Recovered, Spanned};
+use crate::solution::{self, Error, Solution};
+use crate::timing;
+
+pub struct Day__N__;
+
+impl Solution for Day__N__ {
+    type Parsed = Vec<parser::Line>;
+
+    fn tokens(input: &str) -> solution::Result<Vec<String>> {
+        let (tokens, errors) = lexer::Token::lex_recovering(input);
+        if !errors.is_empty() {
+            return Err(Error::input(input, errors));
+        }
+        Ok(solution::debug_lines(&tokens))
+    }
+
+    fn parse(input: &str) -> solution::Result<Self::Parsed> {
+        let (tokens, lex_errors) = timing::phase("lex", || lexer::Token::lex_recovering(input));
+        let (lines, parse_errors) = timing::phase("parse", || parser::parse(tokens));
+        let errors = pt::merge_errors(lex_errors, parse_errors);
+        if !errors.is_empty() {
+            return Err(Error::input(input, errors));
+        }
+        Ok(lines)
+    }
+
+    fn part1(lines: &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(evaluator::eval_part1(lines))
+    }
+
+    fn part2(lines: &Self::Parsed) -> solution::Result<impl Display> {
+        Ok(evaluator::eval_part2(lines))
+    }
+}
+
+mod lexer {
+    use super::*;
+
+    #[derive(Debug, Lexer)]
+    pub enum Token {
+        #[regex_number]
+        Number(usize),
+        #[token("\n")]
+        Newline,
+    }
+}
+
+mod parser {
+    use super::lexer::Token;
+    use super::*;
+
+    #[derive(Debug)]
+    pub struct Line {
+        pub numbers: Vec<usize>,
+    }
+
+    /// Parses every line it can, skipping to the next line after a bad one.
+    pub fn parse(tokens: Vec<Spanned<Token>>) -> Recovered<Vec<Line>> {
+        let (lines, errors) = pt::transform_recovering(
+            tokens,
+            |token| pt::is_token(&Token::Newline, &token.node),
+            parse_line,
+        );
+        (lines.into_iter().map(|line| line.node).collect(), errors)
+    }
+
+    fn parse_line(tokens: &Vec<Spanned<Token>>, pos: pt::Index) -> ParseResult<(pt::Index, Line)> {
+        let (pos, numbers) = pt::parse_numbers(tokens, pos, Token::Number(0), |token| match token {
+            Token::Number(number) => number,
+            _ => unreachable!(),
+        });
+        let pos = pt::expect_token_at(tokens, pos, Token::Newline)?;
+        Ok((pos, Line { numbers }))
+    }
+}
+
+mod evaluator {
+    use super::parser::Line;
+
+    pub fn eval_part1(lines: &Vec<Line>) -> usize {
+        lines.iter().flat_map(|line| &line.numbers).sum()
+    }
+
+    pub fn eval_part2(lines: &Vec<Line>) -> usize {
+        lines.iter().flat_map(|line| &line.numbers).max().copied().unwrap_or(0)
+    }
+}
+"#;
+
+/// What the new day's module declaration is marked with in `days/mod.rs`.
+const MODULE_ATTRIBUTE: &str = "#[allow(clippy::ptr_arg)]";
+
+/// The new day's example test, ignored until the example is filled in.
+const EXAMPLE_TEMPLATE: &str = r#"
+#[test]
+#[ignore = "day __N__'s example hasn't been filled in"]
+fn day__N__() {
+    let input = "\
+";
+    assert_eq!(part1::<Day__N__>(input), "");
+    assert_eq!(part2::<Day__N__>(input), "");
+}
+"#;
+
+/// Creates day `number` in the crate at `root`: its module, its place in the
+/// registry, an example test stub and, unless there is one already, an empty
+/// input file. Returns every file written. An existing day is never
+/// overwritten.
+pub fn create(root: &Path, number: usize) -> io::Result<Vec<PathBuf>> {
+    let registry = root.join("src/days/mod.rs");
+    let days = fs::read_to_string(&registry).map_err(|err| {
+        let message = format!(
+            "can't read {}: {err}; run aoc new from the crate's root",
+            registry.display()
+        );
+        io::Error::new(err.kind(), message)
+    })?;
+
+    let module = root.join(format!("src/days/day{number}.rs"));
+    if module.exists() || days.contains(&format!("pub mod day{number};")) {
+        let message = format!("day {number} already exists");
+        return Err(io::Error::new(ErrorKind::AlreadyExists, message));
+    }
+
+    let days = register(&days, number)?;
+    write_new(&module, &fill(DAY_TEMPLATE, number))?;
+    fs::write(&registry, days)?;
+    let written = vec![module, registry];
+
+    let examples = root.join("tests/examples.rs");
+    let written = match fs::read_to_string(&examples) {
+        Ok(source) => {
+            fs::write(&examples, add_example(&source, number))?;
+            append(written, examples)
+        }
+        Err(err) if err.kind() == ErrorKind::NotFound => written,
+        Err(err) => return Err(err),
+    };
+
+    let Input::File(input) = Input::for_day(number) else {
+        return Ok(written);
+    };
+    let input = root.join(input.strip_prefix(".").unwrap_or(&input));
+    if input.exists() {
+        return Ok(written);
+    }
+    if let Some(dir) = input.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    write_new(&input, "")?;
+    Ok(append(written, input))
+}
+
+/// Adds day `number` to the registry in `days`, the source of `days/mod.rs`:
+/// its module declaration and `MODULE_ATTRIBUTE`, kept in the order `rustfmt`
+/// sorts them in, and its entry in `DAYS`, kept in order of day number.
+pub fn register(days: &str, number: usize) -> io::Result<String> {
+    let lines: Vec<&str> = days.lines().collect();
+    let day = |line: &str, prefix: &str, suffix: &str| {
+        line.strip_prefix(prefix)?
+            .strip_suffix(suffix)?
+            .parse::<usize>()
+            .ok()
+    };
+
+    let module = format!("pub mod day{number};");
+    let lines = insert_sorted(&lines, &[MODULE_ATTRIBUTE, &module], |line| {
+        let name = line.strip_prefix("pub mod ")?.strip_suffix(";")?;
+        day(name, "day", "").map(|_| name)
+    })
+    .ok_or_else(|| invalid("no `pub mod dayN;` declarations to add to"))?;
+
+    // An entry starts a line before its number.
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const DAYS"))
+        .ok_or_else(|| invalid("no `DAYS` registry to add to"))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| *line == "];")
+            .ok_or_else(|| invalid("no end to the `DAYS` registry"))?;
+    let entry = [
+        "    Day {".to_string(),
+        format!("        number: {number},"),
+        format!("        solve: solve::<day{number}::Day{number}>,"),
+        format!("        time: time::<day{number}::Day{number}>,"),
+        format!("        dump: dump::<day{number}::Day{number}>,"),
+        "    },".to_string(),
+    ];
+    let entry: Vec<&str> = entry.iter().map(String::as_str).collect();
+    let at = lines[start..end]
+        .iter()
+        .position(|line| day(line, "        number: ", ",").is_some_and(|day| day > number))
+        .map_or(end, |pos| start + pos - 1);
+
+    let lines = [&lines[..at], &entry, &lines[at..]].concat();
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds day `number`'s example test stub to `examples`, the source of
+/// `tests/examples.rs`, along with the import it needs.
+pub fn add_example(examples: &str, number: usize) -> String {
+    let lines: Vec<&str> = examples.lines().collect();
+    let import = format!("use aoc_2023::days::day{number}::Day{number};");
+    let lines = insert_sorted(&lines, &[&import], |line| {
+        let path = line.strip_prefix("use aoc_2023::days::")?;
+        Some(path.split_once("::")?.0)
+    })
+    .unwrap_or(lines);
+    lines.join("\n") + "\n" + &fill(EXAMPLE_TEMPLATE, number)
+}
+
+/// Inserts `new` among the lines `module` finds a day's module name in,
+/// where `rustfmt` would sort it, or gives `None` if there are no such lines.
+/// The name is in the last line of `new`, and any lines before it are its
+/// attributes, as any `#[..]` lines above a peer are the peer's.
+fn insert_sorted<'a>(
+    lines: &[&'a str],
+    new: &[&'a str],
+    module: impl Fn(&str) -> Option<&str>,
+) -> Option<Vec<&'a str>> {
+    let name = module(new.last()?)?;
+    let peers: Vec<(usize, &str)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(pos, line)| Some((pos, module(line)?)))
+        .collect();
+    let at = match peers.iter().find(|(_, peer)| *peer > name) {
+        Some((pos, _)) => lines[..*pos]
+            .iter()
+            .rposition(|line| !line.starts_with("#["))
+            .map_or(0, |pos| pos + 1),
+        None => peers.last()?.0 + 1,
+    };
+    Some([&lines[..at], new, &lines[at..]].concat())
+}
+
+fn fill(template: &str, number: usize) -> String {
+    template.replace("__N__", &number.to_string())
+}
+
+/// Writes a file that mustn't already exist.
+fn write_new(path: &Path, contents: &str) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(contents.as_bytes())
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message.to_string())
+}
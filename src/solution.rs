@@ -0,0 +1,111 @@
+//! What every day's puzzle looks like to the code that runs, tests and
+//! benchmarks it.
+
+use std::error;
+use std::fmt::{self, Display};
+use std::io::{self, BufRead};
+
+use crate::diagnostics::Excerpt;
+use crate::parsetools::{ParseError, Spanned};
+use crate::read::{normalize, read_all};
+use crate::stream;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Why a puzzle couldn't be solved.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// Everything found wrong with the input.
+    Input(Vec<Excerpt>),
+}
+
+impl Error {
+    /// `errors` found in `source`.
+    pub fn input(source: &str, errors: Vec<Spanned<ParseError>>) -> Error {
+        Error::Input(Excerpt::all(source, errors))
+    }
+
+    /// Renders the error rustc style, naming the input `path`. Like
+    /// `diagnostics::render`, the first line is the bare message, and every
+    /// error after the first starts with `error: `.
+    pub fn render(&self, path: &str) -> String {
+        match self {
+            Error::Io(err) => err.to_string(),
+            Error::Input(excerpts) => excerpts
+                .iter()
+                .map(|excerpt| excerpt.render(path))
+                .collect::<Vec<_>>()
+                .join("\n\nerror: "),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => err.fmt(f),
+            Error::Input(excerpts) => {
+                let errors: Vec<String> = excerpts
+                    .iter()
+                    .map(|excerpt| excerpt.error.to_string())
+                    .collect();
+                write!(f, "{}", errors.join("\n"))
+            }
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<stream::Error> for Error {
+    fn from(err: stream::Error) -> Self {
+        match err {
+            stream::Error::Io(err) => Error::Io(err),
+            stream::Error::Parse(excerpt) => Error::Input(vec![*excerpt]),
+        }
+    }
+}
+
+/// A day's puzzle: how to parse its input, and how to answer each part from
+/// what was parsed.
+pub trait Solution {
+    /// What the input parses to. It's shown by `aoc run --dump-ast`.
+    type Parsed: fmt::Debug;
+
+    /// The input's tokens as `aoc run --dump-tokens` shows them, a line
+    /// each, with their spans where the lexer keeps them.
+    fn tokens(input: &str) -> Result<Vec<String>>;
+
+    /// Parses the whole input, which has been through `read::normalize`.
+    fn parse(input: &str) -> Result<Self::Parsed>;
+
+    /// Parses the input as it's read from `reader`. By default it's read
+    /// whole and handed to `parse`; days that parse a line at a time override
+    /// this so they never hold the whole input.
+    fn parse_reader(reader: impl BufRead) -> Result<Self::Parsed> {
+        Self::parse(&normalize(read_all(reader)?))
+    }
+
+    /// Errors `parse` kept back for a part to report, where a part reads the
+    /// input its own way. `aoc run --dump-ast` stops before either part runs,
+    /// so it reports them from here.
+    fn check(_parsed: &Self::Parsed) -> Result<()> {
+        Ok(())
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<impl Display>;
+
+    fn part2(parsed: &Self::Parsed) -> Result<impl Display>;
+}
+
+/// Each of `items` on a line of its own, for `Solution::tokens`.
+pub fn debug_lines<T: fmt::Debug>(items: &[T]) -> Vec<String> {
+    items.iter().map(|item| format!("{item:?}")).collect()
+}
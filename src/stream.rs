@@ -0,0 +1,268 @@
+//! Lexing and parsing a line at a time over any `io::Read` wrapped in a
+//! `BufReader`, such as a file, stdin or a pipe, for inputs too large to read
+//! into memory at once. Only the current line and the few before it are ever
+//! held, so tokens can't borrow from the input the way `lextools` tokens can.
+//! Errors can't show the lines after them, since they haven't been read yet.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead, ErrorKind};
+
+use crate::diagnostics::{Excerpt, CONTEXT_LINES};
+use crate::functools::*;
+use crate::parsetools::lextools::{self as lt, Lexer};
+use crate::parsetools::{self as pt, Index, Location, ParseError, ParseResult, Span, Spanned};
+use crate::read::normalize;
+use crate::timing;
+
+/// Why a streamed token or record couldn't be produced.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// A lexing or parsing error, with the line of input it was found on.
+    Parse(Box<Excerpt>),
+}
+
+impl Error {
+    /// Renders the error like `diagnostics::render`, with only the lines
+    /// before the offending one as context.
+    pub fn render(&self, path: &str) -> String {
+        match self {
+            Error::Io(err) => err.to_string(),
+            Error::Parse(excerpt) => excerpt.render(path),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => err.fmt(f),
+            Error::Parse(excerpt) => excerpt.error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// Lexes `reader` a line at a time, yielding tokens as they're needed. Each
+/// line is normalized like `read::normalize` first, and `step` reads tokens
+/// from it just like it would for `lextools::lex`. Lexing is timed as the
+/// `lex` phase, even when it happens inside a day's `parse` phase.
+pub fn lex<R, F, U>(reader: R, step: F) -> Tokens<R, F, U>
+where
+    R: BufRead,
+    F: for<'a> FnMut(&'a str, Index) -> ParseResult<(Index, U)>,
+{
+    Tokens {
+        reader,
+        step,
+        pending: VecDeque::new(),
+        text: String::new(),
+        before: VecDeque::new(),
+        line: Location::default(),
+        read: 0,
+    }
+}
+
+/// `lex` with a `Lexer`'s own `step`, for a token type that doesn't borrow
+/// from the input.
+pub fn from_lexer<R, T>(reader: R) -> Tokens<R, Step<T>, T>
+where
+    R: BufRead,
+    T: for<'a> Lexer<'a>,
+{
+    lex(reader, |input, pos| T::step(input, pos))
+}
+
+/// A lexing step that works on any line, whatever its lifetime.
+pub type Step<T> = fn(&str, Index) -> ParseResult<(Index, T)>;
+
+/// The tokens of a streamed input. See `lex`.
+pub struct Tokens<R, F, U> {
+    reader: R,
+    step: F,
+    /// Tokens lexed from `text` but not yet yielded.
+    pending: VecDeque<Spanned<U>>,
+    /// The line most recently read, normalized.
+    text: String,
+    /// Up to `CONTEXT_LINES` lines read before `text`, for error excerpts.
+    before: VecDeque<String>,
+    /// Where `text` starts.
+    line: Location,
+    /// Bytes read so far, before normalizing.
+    read: usize,
+}
+
+impl<R, F, U> Tokens<R, F, U>
+where
+    R: BufRead,
+    F: for<'a> FnMut(&'a str, Index) -> ParseResult<(Index, U)>,
+{
+    /// Reads and lexes the next line into `pending`, returning `false` at the
+    /// end of the input.
+    fn next_line(&mut self) -> Result<bool, Error> {
+        let mut bytes = Vec::new();
+        let len = self.reader.read_until(b'\n', &mut bytes)?;
+        if len == 0 {
+            return Ok(false);
+        }
+
+        let text = String::from_utf8(bytes).map_err(|err| {
+            let offset = self.read + err.utf8_error().valid_up_to();
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid UTF-8 at byte {offset}"),
+            )
+        })?;
+        self.read += len;
+
+        let line = Location {
+            line: self.line.line + self.text.matches('\n').count(),
+            column: 1,
+            offset: self.line.offset + self.text.len(),
+        };
+        let previous = std::mem::replace(&mut self.text, normalize(text));
+        if !previous.is_empty() {
+            self.before.push_back(previous);
+        }
+        if self.before.len() > CONTEXT_LINES {
+            self.before.pop_front();
+        }
+        self.line = line;
+
+        let tokens = timing::phase("lex", || lt::lex(&self.text, &mut self.step))
+            .map_err(|error| self.error(Spanned::new(error.node, shift(error.span, line))))?;
+        self.pending = tokens
+            .into_iter()
+            .map(|token| Spanned::new(token.node, shift(token.span, line)))
+            .collect();
+        Ok(true)
+    }
+
+    /// Attaches the current line and those before it to `error`, found in it.
+    fn error(&self, error: Spanned<ParseError>) -> Error {
+        Error::Parse(Box::new(Excerpt {
+            error,
+            first_line: self.line.line - self.before.len(),
+            text: self
+                .before
+                .iter()
+                .fold(String::new(), |text, line| text + line)
+                + &self.text,
+        }))
+    }
+}
+
+impl<R, F, U> Iterator for Tokens<R, F, U>
+where
+    R: BufRead,
+    F: for<'a> FnMut(&'a str, Index) -> ParseResult<(Index, U)>,
+{
+    type Item = Result<Spanned<U>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        return trampoline((), |()| aux(self));
+
+        fn aux<R, F, U>(
+            tokens: &mut Tokens<R, F, U>,
+        ) -> Bounce<(), Option<Result<Spanned<U>, Error>>>
+        where
+            R: BufRead,
+            F: for<'a> FnMut(&'a str, Index) -> ParseResult<(Index, U)>,
+        {
+            if let Some(token) = tokens.pending.pop_front() {
+                return Bounce::Done(Some(Ok(token)));
+            }
+
+            match tokens.next_line() {
+                Ok(true) => Bounce::Recur(()),
+                Ok(false) => Bounce::Done(None),
+                Err(err) => Bounce::Done(Some(Err(err))),
+            }
+        }
+    }
+}
+
+/// Moves a span measured from the start of a line to where the line starts.
+fn shift(span: Span, line: Location) -> Span {
+    let shift = |location: Location| Location {
+        line: location.line + line.line - 1,
+        column: location.column,
+        offset: location.offset + line.offset,
+    };
+    Span {
+        start: shift(span.start),
+        end: shift(span.end),
+    }
+}
+
+/// Parses `tokens` a record at a time, where a record runs up to and
+/// including the first token `is_sync` accepts, usually a newline. `parse`
+/// must consume the whole record.
+///
+/// A record that fails to lex or parse yields its error, and the next record
+/// carries on after it, like `parsetools::transform_recovering`.
+pub fn records<R, F, T, U>(
+    mut tokens: Tokens<R, F, T>,
+    is_sync: impl Fn(&T) -> bool,
+    mut parse: impl FnMut(&Vec<Spanned<T>>, Index) -> ParseResult<(Index, U)>,
+) -> impl Iterator<Item = Result<U, Error>>
+where
+    R: BufRead,
+    F: for<'a> FnMut(&'a str, Index) -> ParseResult<(Index, T)>,
+    T: fmt::Debug,
+{
+    std::iter::from_fn(move || {
+        let record = try_trampoline(Vec::new(), |record| match tokens.next() {
+            Some(Ok(token)) if is_sync(&token.node) => Ok(Bounce::Done(append(record, token))),
+            Some(Ok(token)) => Ok(Bounce::Recur(append(record, token))),
+            Some(Err(err)) => Err(err),
+            None => Ok(Bounce::Done(record)),
+        });
+
+        match record {
+            Ok(record) if record.is_empty() => None,
+            Ok(record) => {
+                Some(parse_record(&record, &mut parse).map_err(|error| tokens.error(error)))
+            }
+            Err(err) => Some(Err(err)),
+        }
+    })
+}
+
+/// Runs `parse` over the whole of `record`.
+fn parse_record<T: fmt::Debug, U>(
+    record: &Vec<Spanned<T>>,
+    parse: &mut impl FnMut(&Vec<Spanned<T>>, Index) -> ParseResult<(Index, U)>,
+) -> Result<U, Spanned<ParseError>> {
+    let (end, output) = parse(record, 0).map_err(|error| pt::locate(record, error))?;
+    if !is_end(record, end) {
+        return Err(pt::locate(
+            record,
+            pt::unexpected(record, end, "the end of the line"),
+        ));
+    }
+    Ok(output)
+}
+
+/// Drains `records`, keeping what parsed and the errors for what didn't. An
+/// I/O error ends the input, so it's returned straight away.
+pub fn collect<U>(
+    mut records: impl Iterator<Item = Result<U, Error>>,
+) -> io::Result<(Vec<U>, Vec<Excerpt>)> {
+    records.try_fold(
+        (Vec::new(), Vec::new()),
+        |(outputs, errors), record| match record {
+            Ok(output) => Ok((append(outputs, output), errors)),
+            Err(Error::Io(err)) => Err(err),
+            Err(Error::Parse(excerpt)) => Ok((outputs, append(errors, *excerpt))),
+        },
+    )
+}
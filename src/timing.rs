@@ -0,0 +1,125 @@
+//! Timing the phases a day goes through, such as lexing, parsing and each
+//! part's evaluation, for `aoc bench`. Phases are only timed inside `record`,
+//! so marking one costs next to nothing otherwise.
+
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+use crate::functools::append;
+
+/// How long each phase took, in the order the phases first ran.
+pub type Timings = Vec<(&'static str, Duration)>;
+
+struct Recorder {
+    timings: Timings,
+    /// Time spent in phases nested inside the ones still running, innermost
+    /// last.
+    nested: Vec<Duration>,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, timing the phases it marks with `phase`.
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Timings) {
+    let recorder = Recorder {
+        timings: Vec::new(),
+        nested: Vec::new(),
+    };
+    let outer = RECORDER.replace(Some(recorder));
+    let output = f();
+    let recorder = RECORDER.replace(outer).expect("the recorder is put back");
+    (output, recorder.timings)
+}
+
+/// Runs `f` as the phase `name`. A phase's time leaves out the phases nested
+/// inside it, and a phase that runs more than once adds up.
+pub fn phase<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    let is_recording = RECORDER.with_borrow_mut(|recorder| match recorder {
+        Some(recorder) => {
+            if !recorder.timings.iter().any(|(phase, _)| *phase == name) {
+                recorder.timings.push((name, Duration::ZERO));
+            }
+            recorder.nested.push(Duration::ZERO);
+            true
+        }
+        None => false,
+    });
+    if !is_recording {
+        return f();
+    }
+
+    let start = Instant::now();
+    let output = f();
+    let elapsed = start.elapsed();
+
+    RECORDER.with_borrow_mut(|recorder| {
+        let Some(recorder) = recorder else {
+            return;
+        };
+        let nested = recorder.nested.pop().unwrap_or_default();
+        if let Some(parent) = recorder.nested.last_mut() {
+            *parent += elapsed;
+        }
+
+        let own = elapsed.saturating_sub(nested);
+        if let Some((_, total)) = recorder
+            .timings
+            .iter_mut()
+            .find(|(phase, _)| *phase == name)
+        {
+            *total += own;
+        }
+    });
+    output
+}
+
+/// The spread of a phase's times over several runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Summary {
+    /// Summarizes `samples`, or gives `None` if there are none. With an even
+    /// number of samples the median is the lower of the middle two.
+    pub fn of(samples: &[Duration]) -> Option<Summary> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        Some(Summary {
+            min: *sorted.first()?,
+            median: sorted[(sorted.len() - 1) / 2],
+            max: *sorted.last()?,
+        })
+    }
+}
+
+/// Summarizes each phase over `runs`, in the order the phases first ran.
+pub fn summarize(runs: &[Timings]) -> Vec<(&'static str, Summary)> {
+    let phases = runs
+        .iter()
+        .flatten()
+        .fold(Vec::new(), |phases, (phase, _)| {
+            if phases.contains(phase) {
+                phases
+            } else {
+                append(phases, *phase)
+            }
+        });
+
+    phases
+        .into_iter()
+        .filter_map(|phase| {
+            let samples: Vec<Duration> = runs
+                .iter()
+                .flatten()
+                .filter(|(name, _)| *name == phase)
+                .map(|(_, duration)| *duration)
+                .collect();
+            Summary::of(&samples).map(|summary| (phase, summary))
+        })
+        .collect()
+}
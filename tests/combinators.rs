@@ -0,0 +1,93 @@
+use aoc_2023::parsetools::combinators::*;
+use aoc_2023::parsetools::{unexpected, Index, ParseError, ParseResult, Span, Spanned};
+
+#[derive(Debug)]
+enum Token {
+    Number(usize),
+    Comma,
+    Newline,
+}
+
+fn tokens(tokens: Vec<Token>) -> Vec<Spanned<Token>> {
+    tokens
+        .into_iter()
+        .map(|token| Spanned::new(token, Span::default()))
+        .collect()
+}
+
+fn number(tokens: &Vec<Spanned<Token>>, pos: Index) -> ParseResult<(Index, usize)> {
+    match tokens.get(pos).map(|token| &token.node) {
+        Some(Token::Number(number)) => Ok((pos + 1, *number)),
+        _ => Err(unexpected(tokens, pos, "a number")),
+    }
+}
+
+#[test]
+fn many_stops_where_the_parser_stops_matching() {
+    use Token::*;
+    let input = tokens(vec![Number(1), Number(2), Newline]);
+
+    assert_eq!(many(number)(&input, 0), Ok((2, vec![1, 2])));
+    assert_eq!(many(number)(&input, 2), Ok((2, vec![])));
+}
+
+#[test]
+fn many1_needs_at_least_one_match() {
+    use Token::*;
+    let input = tokens(vec![Newline]);
+
+    assert!(many1(number)(&input, 0).is_err());
+}
+
+#[test]
+fn sep_by_reads_separated_items() {
+    use Token::*;
+    let input = tokens(vec![Number(1), Comma, Number(2), Newline]);
+
+    assert_eq!(sep_by(number, token(Comma))(&input, 0), Ok((3, vec![1, 2])));
+}
+
+#[test]
+fn sep_by_passes_on_errors_after_a_separator() {
+    use Token::*;
+    let input = tokens(vec![Number(1), Comma, Newline]);
+
+    let err = sep_by(number, token(Comma))(&input, 0).unwrap_err();
+    assert_eq!(err.pos(), 2);
+}
+
+#[test]
+fn optional_gives_none_without_consuming() {
+    use Token::*;
+    let input = tokens(vec![Newline]);
+
+    assert_eq!(optional(number)(&input, 0), Ok((0, None)));
+}
+
+#[test]
+fn alt_lists_both_expectations() {
+    use Token::*;
+    let input = tokens(vec![Newline]);
+    let comma =
+        |tokens: &Vec<Spanned<Token>>, pos| token(Comma)(tokens, pos).map(|(pos, _)| (pos, 0));
+
+    assert_eq!(
+        alt(number, comma)(&input, 0),
+        Err(ParseError::UnexpectedToken {
+            pos: 0,
+            expected: "a number or Comma".to_string(),
+            found: "Newline".to_string(),
+        })
+    );
+}
+
+#[test]
+fn terminated_by_keeps_only_the_item() {
+    use Token::*;
+    let input = tokens(vec![Number(7), Newline]);
+
+    assert_eq!(
+        terminated_by(sequence(number, optional(number)), token(Newline))(&input, 0),
+        Ok((2, (7, None)))
+    );
+}
@@ -0,0 +1,162 @@
+//! Every day's puzzle examples, run through the same parsing and answering as
+//! the real input.
+
+use aoc_2023::days::day1::Day1;
+use aoc_2023::days::day2::Day2;
+use aoc_2023::days::day3::Day3;
+use aoc_2023::days::day4::Day4;
+use aoc_2023::days::day5::Day5;
+use aoc_2023::days::day6::Day6;
+use aoc_2023::days::day7::Day7;
+use aoc_2023::days::Part;
+use aoc_2023::solution::Solution;
+
+fn part1<S: Solution>(input: &str) -> String {
+    answer::<S>(input, Part::One)
+}
+
+fn part2<S: Solution>(input: &str) -> String {
+    answer::<S>(input, Part::Two)
+}
+
+/// Reads `input` like the runner reads a file, and answers `part` from it.
+fn answer<S: Solution>(input: &str, part: Part) -> String {
+    let parsed =
+        S::parse_reader(input.as_bytes()).unwrap_or_else(|err| panic!("{}", err.render("example")));
+    let answer = match part {
+        Part::One => S::part1(&parsed).map(|answer| answer.to_string()),
+        Part::Two => S::part2(&parsed).map(|answer| answer.to_string()),
+    };
+    answer.unwrap_or_else(|err| panic!("{}", err.render("example")))
+}
+
+#[test]
+fn day1() {
+    let input = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+    assert_eq!(part1::<Day1>(input), "142");
+
+    let input = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+    assert_eq!(part2::<Day1>(input), "281");
+}
+
+#[test]
+fn day2() {
+    let input = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+    assert_eq!(part1::<Day2>(input), "8");
+    assert_eq!(part2::<Day2>(input), "2286");
+}
+
+#[test]
+fn day3() {
+    let input = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+    assert_eq!(part1::<Day3>(input), "4361");
+    assert_eq!(part2::<Day3>(input), "467835");
+}
+
+#[test]
+fn day4() {
+    let input = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+    assert_eq!(part1::<Day4>(input), "13");
+    assert_eq!(part2::<Day4>(input), "30");
+}
+
+#[test]
+fn day5() {
+    let input = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+    assert_eq!(part1::<Day5>(input), "35");
+    assert_eq!(part2::<Day5>(input), "46");
+}
+
+#[test]
+fn day6() {
+    let input = "\
+Time:      7  15   30
+Distance:  9  40  200
+";
+    assert_eq!(part1::<Day6>(input), "288");
+    assert_eq!(part2::<Day6>(input), "71503");
+}
+
+#[test]
+fn day7() {
+    let input = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+    assert_eq!(part1::<Day7>(input), "6440");
+    assert_eq!(part2::<Day7>(input), "5905");
+}
@@ -0,0 +1,28 @@
+use aoc_2023::json::Value;
+
+#[test]
+fn objects_keep_their_member_order() {
+    let value = Value::object([
+        ("day", 7usize.into()),
+        ("answer", "6440".into()),
+        ("elapsed_ns", 1234u128.into()),
+        ("parts", vec![1usize, 2].into()),
+        ("input", Value::Null),
+        ("ok", true.into()),
+    ]);
+    assert_eq!(
+        value.to_string(),
+        r#"{"day":7,"answer":"6440","elapsed_ns":1234,"parts":[1,2],"input":null,"ok":true}"#
+    );
+}
+
+#[test]
+fn strings_are_escaped() {
+    let value = Value::from("C:\\input\\\"day1\".txt\n\t\u{1}é");
+    assert_eq!(value.to_string(), r#""C:\\input\\\"day1\".txt\n\t\u0001é""#);
+}
+
+#[test]
+fn huge_numbers_saturate() {
+    assert_eq!(Value::from(u128::MAX), Value::Number(i128::MAX));
+}
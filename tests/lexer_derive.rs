@@ -0,0 +1,87 @@
+use aoc_2023::parsetools::lextools::Lexer;
+use aoc_2023::parsetools::ParseError;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Color {
+    Red,
+    Blue,
+}
+
+#[derive(Debug, PartialEq, Lexer)]
+enum Token<'a> {
+    #[regex_number]
+    Number(usize),
+    #[ident]
+    Ident(&'a str),
+    #[token("red", Color::Red)]
+    #[token("blue", Color::Blue)]
+    Color(Color),
+    #[token("-")]
+    Dash,
+    #[token("->")]
+    Arrow,
+    #[token("\n")]
+    Newline,
+}
+
+#[derive(Debug, PartialEq, Lexer)]
+enum Keyword {
+    #[token("Card")]
+    Card,
+    #[token(":")]
+    Colon,
+}
+
+fn lex(input: &str) -> Vec<Token<'_>> {
+    Token::lex(input)
+        .unwrap()
+        .into_iter()
+        .map(|token| token.node)
+        .collect()
+}
+
+#[test]
+fn keywords_carry_their_values() {
+    use Token::*;
+    assert_eq!(
+        lex("3 red 4 blue green\n"),
+        vec![
+            Number(3),
+            Color(self::Color::Red),
+            Number(4),
+            Color(self::Color::Blue),
+            Ident("green"),
+            Newline
+        ]
+    );
+}
+
+#[test]
+fn longer_literals_win() {
+    use Token::*;
+    assert_eq!(
+        lex("a->b-c"),
+        vec![Ident("a"), Arrow, Ident("b"), Dash, Ident("c")]
+    );
+}
+
+#[test]
+fn unknown_identifiers_are_errors_without_ident() {
+    let err = Keyword::lex("Card: Crad").unwrap_err();
+
+    assert_eq!(
+        err.node,
+        ParseError::UnknownIdentifier {
+            pos: 6,
+            ident: "Crad".to_string(),
+            expected: "\"Card\"".to_string(),
+        }
+    );
+}
+
+#[test]
+fn unmatched_characters_are_errors() {
+    let err = Keyword::lex("Card?").unwrap_err();
+
+    assert_eq!(err.node, ParseError::UnknownCharacter { pos: 4, c: '?' });
+}
@@ -0,0 +1,56 @@
+use std::path::{Path, PathBuf};
+
+use aoc_2023::days::{Part, DAYS};
+use aoc_2023::manifest::{self, Entry};
+use aoc_2023::solution::Error;
+
+#[test]
+fn entries_skip_comments_and_blank_lines() {
+    let source = "\
+# day part input answer
+1 1 input/day1.txt 54632
+
+1 2 input/day1.txt 54019  # after a fix
+";
+    let entries = manifest::parse(source, Path::new("answers")).unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            Entry {
+                day: 1,
+                part: Part::One,
+                input: PathBuf::from("answers/input/day1.txt"),
+                answer: "54632".to_string(),
+            },
+            Entry {
+                day: 1,
+                part: Part::Two,
+                input: PathBuf::from("answers/input/day1.txt"),
+                answer: "54019".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn every_bad_line_gets_an_error() {
+    let source = "1 3 a.txt 4\n2 1 b.txt 5\nx 1 c.txt 6\n3 2 d.txt\n";
+    let Err(Error::Input(excerpts)) = manifest::parse(source, Path::new("")) else {
+        panic!("expected errors");
+    };
+    let lines: Vec<_> = excerpts
+        .iter()
+        .map(|excerpt| excerpt.error.span.start.line)
+        .collect();
+    assert_eq!(lines, vec![1, 3, 4]);
+}
+
+#[test]
+fn the_example_manifest_covers_every_day() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.example.txt");
+    let entries = manifest::read(&path).unwrap();
+    for day in DAYS {
+        assert!(entries.iter().any(|entry| entry.day == day.number));
+    }
+    assert!(entries.iter().all(|entry| entry.input.exists()));
+}
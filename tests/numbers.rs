@@ -0,0 +1,82 @@
+use aoc_2023::parsetools::lextools::{self as lt, Lexer};
+use aoc_2023::parsetools::{self as pt, ParseError};
+
+#[derive(Debug, PartialEq, Lexer)]
+enum Token {
+    #[regex_number(signed)]
+    Number(i64),
+    #[token("-")]
+    Dash,
+    #[token(",")]
+    Comma,
+}
+
+fn lex(input: &str) -> Vec<Token> {
+    Token::lex(input)
+        .unwrap()
+        .into_iter()
+        .map(|token| token.node)
+        .collect()
+}
+
+#[test]
+fn signs_belong_to_the_number_they_precede() {
+    use Token::*;
+    assert_eq!(
+        lex("-3,+4, - 5,6-7"),
+        vec![
+            Number(-3),
+            Comma,
+            Number(4),
+            Comma,
+            Dash,
+            Number(5),
+            Comma,
+            Number(6),
+            Number(-7)
+        ]
+    );
+}
+
+#[test]
+fn reads_numbers_into_the_requested_width() {
+    assert_eq!(
+        lt::read_signed::<i128>("-170141183460469231731687303715884105728", 0),
+        Ok((40, i128::MIN))
+    );
+    assert_eq!(
+        lt::read_number::<u128>("18446744073709551616 ", 0),
+        Ok((20, 1 << 64))
+    );
+}
+
+#[test]
+fn overflow_is_an_error() {
+    let err = Token::lex("1, 9223372036854775808").unwrap_err();
+
+    assert_eq!(
+        err.node,
+        ParseError::NumberOutOfRange {
+            pos: 3,
+            text: "9223372036854775808".to_string(),
+            ty: "i64".to_string(),
+        }
+    );
+    assert_eq!(err.span.end.column - err.span.start.column, 19);
+}
+
+#[test]
+fn unsigned_numbers_stop_at_a_sign() {
+    assert_eq!(lt::read_number::<u64>("12-3", 0), Ok((2, 12)));
+}
+
+#[test]
+fn parse_numbers_keeps_the_token_type() {
+    let tokens = Token::lex("-1 2 -3,").unwrap();
+    let (pos, numbers) = pt::parse_numbers(&tokens, 0, Token::Number(0), |token| match token {
+        Token::Number(number) => number,
+        _ => unreachable!(),
+    });
+
+    assert_eq!((pos, numbers), (3, vec![-1i64, 2, -3]));
+}
@@ -0,0 +1,94 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use aoc_2023::read::{normalize, read, read_all_ascii, read_ascii, Input};
+
+fn temp_file(name: &str, bytes: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("aoc-2023-{}-{name}", std::process::id()));
+    fs::write(&path, bytes).unwrap();
+    path
+}
+
+#[test]
+fn normalize_turns_crlf_into_lf() {
+    assert_eq!(normalize("a 1\r\nb 2\r\n".to_string()), "a 1\nb 2\n");
+}
+
+#[test]
+fn normalize_turns_lone_cr_into_lf() {
+    assert_eq!(normalize("a 1\rb 2\r".to_string()), "a 1\nb 2\n");
+}
+
+#[test]
+fn normalize_adds_missing_trailing_newline() {
+    assert_eq!(normalize("a 1\nb 2".to_string()), "a 1\nb 2\n");
+}
+
+#[test]
+fn normalize_keeps_existing_trailing_newline() {
+    assert_eq!(normalize("a 1\n".to_string()), "a 1\n");
+}
+
+#[test]
+fn normalize_turns_tabs_into_spaces() {
+    assert_eq!(normalize("Time:\t7\t15\n".to_string()), "Time: 7 15\n");
+}
+
+#[test]
+fn normalize_leaves_empty_input_empty() {
+    assert_eq!(normalize(String::new()), "");
+}
+
+#[test]
+fn read_decodes_utf8() {
+    let path = temp_file("utf8.txt", "Game 1: 3 bléu\n".as_bytes());
+    assert_eq!(read(&path).unwrap(), "Game 1: 3 bléu\n");
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn read_rejects_invalid_utf8() {
+    let path = temp_file("invalid.txt", b"seeds: 1\xff\n");
+    let err = read(&path).unwrap_err();
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "invalid UTF-8 at byte 8");
+}
+
+#[test]
+fn read_ascii_rejects_non_ascii() {
+    let path = temp_file("ascii.txt", "467..114..\n...é......\n".as_bytes());
+    let err = read_ascii(&path).unwrap_err();
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "non-ASCII byte at 14");
+    assert_eq!(read_all_ascii("..*\n".as_bytes()).unwrap(), "..*\n");
+}
+
+#[test]
+fn dash_means_stdin() {
+    assert_eq!(Input::from_arg("-"), Input::Stdin);
+    assert_eq!(Input::from_arg("day5.txt"), Input::File("day5.txt".into()));
+}
+
+#[test]
+fn inputs_are_looked_for_in_aoc_input_dir() {
+    std::env::set_var("AOC_INPUT_DIR", "/puzzles");
+    assert_eq!(Input::for_day(5), Input::File("/puzzles/day5.txt".into()));
+}
+
+#[test]
+fn missing_input_says_how_to_find_another() {
+    let err = Input::File("/nonexistent/day9.txt".into())
+        .read()
+        .unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    assert_eq!(
+        err.to_string(),
+        "input file /nonexistent/day9.txt not found; pass --input <path> or set AOC_INPUT_DIR"
+    );
+}
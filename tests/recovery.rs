@@ -0,0 +1,68 @@
+use aoc_2023::parsetools::lextools::{self as lt, Lexer};
+use aoc_2023::parsetools::{self as pt, Index, ParseError, ParseResult, Spanned};
+
+#[derive(Debug, PartialEq, Lexer)]
+enum Token {
+    #[regex_number]
+    Number(usize),
+    #[token("\n")]
+    Newline,
+}
+
+/// A line of exactly two numbers.
+fn pair(tokens: &Vec<Spanned<Token>>, pos: Index) -> ParseResult<(Index, (usize, usize))> {
+    let number = |pos| match pt::token_at(tokens, pos) {
+        Some(Token::Number(number)) => Ok(*number),
+        _ => Err(pt::unexpected(tokens, pos, "a number")),
+    };
+    let pair = (number(pos)?, number(pos + 1)?);
+    let pos = pt::expect_token_at(tokens, pos + 2, Token::Newline)?;
+    Ok((pos, pair))
+}
+
+fn parse(input: &str) -> (Vec<(usize, usize)>, Vec<Spanned<ParseError>>) {
+    let (tokens, lex_errors) = Token::lex_recovering(input);
+    let (pairs, parse_errors) = pt::transform_recovering(
+        tokens,
+        |token| pt::is_token(&Token::Newline, &token.node),
+        pair,
+    );
+    let pairs = pairs.into_iter().map(|pair| pair.node).collect();
+    (pairs, pt::merge_errors(lex_errors, parse_errors))
+}
+
+fn lines(errors: &[Spanned<ParseError>]) -> Vec<usize> {
+    errors.iter().map(|error| error.span.start.line).collect()
+}
+
+#[test]
+fn every_bad_line_gets_an_error() {
+    let (pairs, errors) = parse("1 2\n3\n4 5\n6 7 8\n9 10\n");
+
+    assert_eq!(pairs, vec![(1, 2), (4, 5), (9, 10)]);
+    assert_eq!(lines(&errors), vec![2, 4]);
+}
+
+#[test]
+fn lexing_errors_hide_knock_on_parsing_errors() {
+    let (pairs, errors) = parse("1 2\n3 x 4\n5 6\n");
+
+    assert_eq!(pairs, vec![(1, 2), (5, 6)]);
+    assert_eq!(lines(&errors), vec![2]);
+    assert_eq!(
+        errors[0].node,
+        ParseError::UnknownCharacter { pos: 6, c: 'x' }
+    );
+}
+
+#[test]
+fn lexing_resumes_at_the_next_line() {
+    let (tokens, errors) = lt::lex_recovering("1 ? 2\n3", Token::step);
+    let tokens: Vec<Token> = tokens.into_iter().map(|token| token.node).collect();
+
+    assert_eq!(
+        tokens,
+        vec![Token::Number(1), Token::Newline, Token::Number(3)]
+    );
+    assert_eq!(lines(&errors), vec![1]);
+}
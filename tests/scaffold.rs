@@ -0,0 +1,72 @@
+use aoc_2023::scaffold;
+
+const REGISTRY: &str = "\
+#[allow(clippy::ptr_arg, clippy::needless_return)]
+pub mod day1;
+#[allow(clippy::ptr_arg)]
+pub mod day2;
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        solve: solve::<day1::Day1>,
+        time: time::<day1::Day1>,
+        dump: dump::<day1::Day1>,
+    },
+    Day {
+        number: 2,
+        solve: solve::<day2::Day2>,
+        time: time::<day2::Day2>,
+        dump: dump::<day2::Day2>,
+    },
+];
+";
+
+#[test]
+fn new_days_are_registered_in_order() {
+    let registry = scaffold::register(REGISTRY, 12).unwrap();
+    let registry = scaffold::register(&registry, 3).unwrap();
+
+    let modules: Vec<&str> = registry
+        .lines()
+        .filter(|line| line.starts_with("pub mod"))
+        .collect();
+    assert_eq!(
+        modules,
+        vec![
+            "pub mod day1;",
+            "pub mod day12;",
+            "pub mod day2;",
+            "pub mod day3;"
+        ]
+    );
+
+    let numbers: Vec<&str> = registry
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("number: "))
+        .collect();
+    assert_eq!(numbers, vec!["1,", "2,", "3,", "12,"]);
+    assert!(registry.contains(
+        "#[allow(clippy::ptr_arg, clippy::needless_return)]\npub mod day1;\n\
+         #[allow(clippy::ptr_arg)]\npub mod day12;\n\
+         #[allow(clippy::ptr_arg)]\npub mod day2;\n\
+         #[allow(clippy::ptr_arg)]\npub mod day3;\n"
+    ));
+    assert!(registry.contains("        solve: solve::<day12::Day12>,\n"));
+}
+
+#[test]
+fn registering_needs_a_registry() {
+    assert!(scaffold::register("fn main() {}\n", 8).is_err());
+}
+
+#[test]
+fn example_stubs_are_ignored_until_filled_in() {
+    let examples = "use aoc_2023::days::day1::Day1;\nuse aoc_2023::days::Part;\n";
+    let examples = scaffold::add_example(examples, 8);
+
+    assert!(examples.starts_with(
+        "use aoc_2023::days::day1::Day1;\nuse aoc_2023::days::day8::Day8;\nuse aoc_2023::days::Part;\n"
+    ));
+    assert!(examples.contains("#[ignore = \"day 8's example hasn't been filled in\"]\nfn day8() {"));
+}
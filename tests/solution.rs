@@ -0,0 +1,160 @@
+use aoc_2023::days::day1::Day1;
+use aoc_2023::days::day2::Day2;
+use aoc_2023::days::day3::Day3;
+use aoc_2023::days::day4::Day4;
+use aoc_2023::days::day5::Day5;
+use aoc_2023::days::day6::Day6;
+use aoc_2023::days::day7::Day7;
+use aoc_2023::solution::Solution;
+
+#[test]
+fn reading_normalizes_the_input() {
+    let games = Day2::parse_reader("Game 1: 3 blue\r\nGame 2: 20 red\r\n".as_bytes()).unwrap();
+    assert_eq!(Day2::part1(&games).unwrap().to_string(), "1");
+}
+
+#[test]
+fn errors_render_every_bad_line() {
+    let err = Day4::parse("Card 1: 1 | 2\nCard 2 1 | 2\nCard 3: x\n").unwrap_err();
+    assert_eq!(
+        err.render("cards.txt"),
+        "expected Colon, found Number(1)
+ --> cards.txt:2:8
+  |
+1 | Card 1: 1 | 2
+2 | Card 2 1 | 2
+  |        ^
+
+error: unknown identifier \"x\", expected \"Card\"
+ --> cards.txt:3:9
+  |
+1 | Card 1: 1 | 2
+2 | Card 2 1 | 2
+3 | Card 3: x
+  |         ^"
+    );
+}
+
+#[test]
+fn a_part_can_fail_without_the_other() {
+    let parsed = Day1::parse("two1nine\n").unwrap();
+    assert_eq!(Day1::part2(&parsed).unwrap().to_string(), "29");
+
+    let parsed = Day1::parse("eightwothree\n").unwrap();
+    let err = Day1::part1(&parsed).err().unwrap();
+    assert_eq!(err.to_string(), "1:1: expected a digit, found Newline");
+    assert_eq!(Day1::part2(&parsed).unwrap().to_string(), "83");
+}
+
+#[test]
+fn every_line_without_a_digit_is_reported() {
+    let parsed = Day1::parse("a1\nb\nc2\nd\n").unwrap();
+    let err = Day1::part1(&parsed).err().unwrap();
+    assert_eq!(
+        err.render("calibration.txt"),
+        "expected a digit, found Newline
+ --> calibration.txt:2:1
+  |
+1 | a1
+2 | b
+  | ^
+3 | c2
+4 | d
+
+error: expected a digit, found Newline
+ --> calibration.txt:4:1
+  |
+2 | b
+3 | c2
+4 | d
+  | ^"
+    );
+}
+
+#[test]
+fn races_need_a_distance_each_and_nothing_after() {
+    let err = Day6::parse("Time: 7 15\nDistance: 9\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "2:1: expected 2 distances, one per race, found 1"
+    );
+
+    let err = Day6::parse("Time: 7\nDistance: 9\nTime: 3\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "3:1: expected the end of the input, found Time"
+    );
+}
+
+#[test]
+fn zeros_are_not_digits() {
+    let parsed = Day1::parse("a0b1\n").unwrap();
+    assert_eq!(Day1::part1(&parsed).unwrap().to_string(), "11");
+    assert!(Day1::tokens("0\n").is_ok());
+
+    let parsed = Day1::parse("a0b\n").unwrap();
+    let err = Day1::part1(&parsed).err().unwrap();
+    assert_eq!(err.to_string(), "1:1: expected a digit, found Newline");
+}
+
+#[test]
+fn tokens_are_dumped_with_their_spans() {
+    let tokens = Day2::tokens("Game 1: 3 blue\n").unwrap();
+    assert_eq!(
+        tokens[..3],
+        ["Game @ 1:1-1:5", "Digit(1) @ 1:6-1:7", "Colon @ 1:7-1:8"]
+    );
+}
+
+#[test]
+fn ragged_schematics_are_rejected() {
+    let err = Day3::parse("467..\n...é.\n").unwrap_err();
+    assert_eq!(err.to_string(), "non-ASCII byte at 9");
+
+    let err = Day3::parse("..*\n1\n").unwrap_err();
+    assert_eq!(
+        err.render("schematic.txt"),
+        "expected 3 columns, found 1
+ --> schematic.txt:2:2
+  |
+1 | ..*
+2 | 1
+  |  ^"
+    );
+}
+
+#[test]
+fn overflowing_numbers_are_reported_whole() {
+    let err = Day7::parse("T55J5 99999999999999999999999\n").unwrap_err();
+    assert_eq!(
+        err.render("hands.txt"),
+        "number 99999999999999999999999 doesn't fit in usize
+ --> hands.txt:1:7
+  |
+1 | T55J5 99999999999999999999999
+  |       ^^^^^^^^^^^^^^^^^^^^^^^"
+    );
+    assert!(Day3::parse("*99999999999999999999999\n").is_err());
+}
+
+#[test]
+fn inputs_without_answers_are_errors() {
+    assert!(Day5::parse("seeds:\n").is_err());
+    assert!(Day6::parse("Time:\nDistance:\n").is_err());
+
+    let parsed = Day5::parse("seeds: 5 0\n").unwrap();
+    assert_eq!(Day5::part1(&parsed).unwrap().to_string(), "0");
+    let err = Day5::part2(&parsed).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "1:8: expected a seed range that isn't empty, found none"
+    );
+}
+
+#[test]
+fn checking_reports_a_single_part_s_errors() {
+    assert!(Day1::check(&Day1::parse("1abc2\n").unwrap()).is_ok());
+
+    let err = Day1::check(&Day1::parse("eightwothree\n").unwrap()).unwrap_err();
+    assert_eq!(err.to_string(), "1:1: expected a digit, found Newline");
+}
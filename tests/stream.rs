@@ -0,0 +1,127 @@
+use std::io::{self, BufReader, Read};
+
+use aoc_2023::parsetools::lextools::Lexer;
+use aoc_2023::parsetools::{self as pt, Index, ParseResult, Spanned};
+use aoc_2023::read::normalize;
+use aoc_2023::stream;
+
+#[derive(Debug, PartialEq, Lexer)]
+enum Token {
+    #[regex_number]
+    Number(usize),
+    #[token("\n")]
+    Newline,
+}
+
+/// A line of numbers.
+fn numbers(tokens: &Vec<Spanned<Token>>, pos: Index) -> ParseResult<(Index, Vec<usize>)> {
+    let (pos, numbers) = pt::parse_numbers(tokens, pos, Token::Number(0), |token| match token {
+        Token::Number(number) => number,
+        _ => unreachable!(),
+    });
+    let pos = pt::expect_token_at(tokens, pos, Token::Newline)?;
+    Ok((pos, numbers))
+}
+
+fn newline(token: &Token) -> bool {
+    pt::is_token(&Token::Newline, token)
+}
+
+/// Produces `line` over and over, `count` times, without ever holding more
+/// than one copy of it.
+struct Repeat {
+    line: &'static [u8],
+    pos: usize,
+    count: usize,
+}
+
+impl Read for Repeat {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.count == 0 {
+            return Ok(0);
+        }
+        let len = buf.len().min(self.line.len() - self.pos);
+        buf[..len].copy_from_slice(&self.line[self.pos..self.pos + len]);
+        self.pos += len;
+        if self.pos == self.line.len() {
+            self.pos = 0;
+            self.count -= 1;
+        }
+        Ok(len)
+    }
+}
+
+#[test]
+fn streamed_spans_match_whole_input_spans() {
+    let input = "1 2\r\n\r\n  3\t4\r\n5";
+    let whole = Token::lex(&normalize(input.to_string())).unwrap();
+    let streamed: Vec<_> = stream::from_lexer(input.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(streamed, whole);
+}
+
+#[test]
+fn bad_records_are_reported_and_skipped() {
+    let input = "1 2\n3 x\n4\n5 +\n";
+    let records = stream::records(stream::from_lexer(input.as_bytes()), newline, numbers);
+    let (parsed, errors) = stream::collect(records).unwrap();
+
+    assert_eq!(parsed, vec![vec![1, 2], vec![4]]);
+    let lines: Vec<_> = errors
+        .iter()
+        .map(|excerpt| excerpt.error.span.start.line)
+        .collect();
+    assert_eq!(lines, vec![2, 4]);
+}
+
+#[test]
+fn errors_render_with_the_lines_before_them() {
+    let input = "1\n2\n3\n4 ?\n5\n";
+    let records = stream::records(stream::from_lexer(input.as_bytes()), newline, numbers);
+    let (_, errors) = stream::collect(records).unwrap();
+
+    assert_eq!(
+        errors[0].render("nums.txt"),
+        "unknown character '?'\n --> nums.txt:4:3\n  |\n2 | 2\n3 | 3\n4 | 4 ?\n  |   ^"
+    );
+}
+
+#[test]
+fn invalid_utf8_is_an_io_error() {
+    let input: &[u8] = b"1\n2 \xff\n";
+    let records = stream::records(stream::from_lexer(input), newline, numbers);
+
+    let err = stream::collect(records).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "invalid UTF-8 at byte 4");
+}
+
+#[test]
+fn streams_inputs_larger_than_what_is_held() {
+    let line: &[u8] = b"12 345 6789\n";
+    let count = 256 * 1024;
+    let reader = BufReader::new(Repeat {
+        line,
+        pos: 0,
+        count,
+    });
+
+    let (sum, errors) = stream::records(stream::from_lexer(reader), newline, numbers).fold(
+        (0, 0),
+        |(sum, errors), record| match record {
+            Ok(numbers) => (sum + numbers.iter().sum::<usize>(), errors),
+            Err(_) => (sum, errors + 1),
+        },
+    );
+
+    assert_eq!((sum, errors), (count * (12 + 345 + 6789), 0));
+}
+
+#[test]
+fn empty_input_has_no_records() {
+    let records = stream::records(stream::from_lexer(io::empty()), newline, numbers);
+
+    assert!(stream::collect(records).unwrap().0.is_empty());
+}
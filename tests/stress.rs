@@ -0,0 +1,124 @@
+use aoc_2023::days::day2::Day2;
+use aoc_2023::days::day4::Day4;
+use aoc_2023::parsetools::lextools as lt;
+use aoc_2023::parsetools::{self as pt, Index, ParseError, ParseResult, Spanned, SpannedResult};
+use aoc_2023::solution::{Error, Solution};
+
+const MEGABYTE: usize = 1024 * 1024;
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Ident(usize),
+    Newline,
+}
+
+fn lex(input: &str) -> SpannedResult<Vec<Spanned<Token>>> {
+    lt::lex(input, |input, pos| {
+        let pos = lt::skip_whitespace(input, pos)?;
+        match lt::peek(input, pos)? {
+            '\n' => Ok((pos + 1, Token::Newline)),
+            c if c.is_alphabetic() => {
+                let (pos, ident) = lt::read_identifier(input, pos)?;
+                Ok((pos, Token::Ident(ident.len())))
+            }
+            c => Err(ParseError::UnknownCharacter { pos, c }),
+        }
+    })
+}
+
+fn generate(lines: usize, width: usize) -> String {
+    format!("{}\n", "a".repeat(width)).repeat(lines)
+}
+
+#[test]
+fn lexes_one_multi_megabyte_token() {
+    let tokens = lex(&generate(1, 4 * MEGABYTE)).unwrap();
+
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[0].node, Token::Ident(4 * MEGABYTE));
+    assert_eq!(tokens[1].span.start.column, 4 * MEGABYTE + 1);
+}
+
+#[test]
+fn lexes_many_tokens_across_megabytes_of_whitespace() {
+    let input = format!("{}word\n", " ".repeat(4 * MEGABYTE));
+    let tokens = lex(&input).unwrap();
+
+    assert_eq!(tokens[0].node, Token::Ident(4));
+    assert_eq!(tokens[0].span.start.offset, 4 * MEGABYTE);
+}
+
+#[test]
+fn lexes_multi_megabyte_input_line_by_line() {
+    let tokens = lex(&generate(4 * 1024, 1023)).unwrap();
+
+    assert_eq!(tokens.len(), 2 * 4 * 1024);
+    assert_eq!(tokens.last().unwrap().span.start.line, 4 * 1024);
+}
+
+#[test]
+fn locates_error_at_the_end_of_multi_megabyte_input() {
+    let mut input = generate(4 * 1024, 1023);
+    input.push('#');
+    let err = lex(&input).unwrap_err();
+
+    assert_eq!(err.span.start.line, 4 * 1024 + 1);
+    assert_eq!(err.span.start.column, 1);
+}
+
+#[test]
+fn transforms_multi_megabyte_input_line_by_line() {
+    let tokens = lex(&generate(4 * 1024, 1023)).unwrap();
+    let lines = pt::transform(tokens, |tokens, pos| match pt::token_at(tokens, pos) {
+        Some(Token::Ident(width)) => {
+            let width = *width;
+            let pos = pt::expect_token_at(tokens, pos + 1, Token::Newline)?;
+            Ok((pos, width))
+        }
+        _ => Err(pt::unexpected(tokens, pos, "an identifier")),
+    })
+    .unwrap();
+
+    assert_eq!(lines.len(), 4 * 1024);
+    assert_eq!(lines.last().unwrap().node, 1023);
+    assert_eq!(lines.last().unwrap().span.end.line, 4 * 1024 + 1);
+}
+
+#[test]
+fn parses_a_multi_megabyte_card() {
+    let numbers = |from: usize| {
+        (from..from + 150_000)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let input = format!("Card 1: {} | {}\n", numbers(100_000), numbers(200_000));
+    assert!(input.len() > 2 * MEGABYTE);
+
+    let cards = Day4::parse(&input).unwrap();
+    assert_eq!(Day4::part2(&cards).unwrap().to_string(), "1");
+}
+
+#[test]
+fn reports_every_bad_line_of_a_large_input() {
+    let err = Day2::parse(&"Game x\n".repeat(20_000)).err().unwrap();
+    let Error::Input(excerpts) = err else {
+        panic!("expected input errors, got {err}");
+    };
+
+    assert_eq!(excerpts.len(), 20_000);
+    assert_eq!(excerpts.last().unwrap().error.span.start.line, 20_000);
+}
+
+#[test]
+fn reads_sequence_longer_than_the_stack() {
+    let input = "7".repeat(4 * MEGABYTE);
+    let result: ParseResult<(Index, usize)> = lt::read_sequence(
+        &input,
+        0,
+        |c| c.is_ascii_digit(),
+        |_, chars| Ok(chars.len()),
+    );
+
+    assert_eq!(result, Ok((4 * MEGABYTE, 4 * MEGABYTE)));
+}
@@ -0,0 +1,57 @@
+use std::thread;
+use std::time::Duration;
+
+use aoc_2023::timing::{self, Summary};
+
+#[test]
+fn nested_phases_are_left_out_of_the_outer_one() {
+    let ((), timings) = timing::record(|| {
+        timing::phase("outer", || {
+            timing::phase("inner", || thread::sleep(Duration::from_millis(20)));
+        });
+        timing::phase("inner", || thread::sleep(Duration::from_millis(20)));
+    });
+
+    let phases: Vec<_> = timings.iter().map(|(phase, _)| *phase).collect();
+    assert_eq!(phases, vec!["outer", "inner"]);
+    assert!(timings[0].1 < Duration::from_millis(20));
+    assert!(timings[1].1 >= Duration::from_millis(40));
+}
+
+#[test]
+fn phases_outside_a_recording_just_run() {
+    assert_eq!(timing::phase("lex", || 42), 42);
+    let ((), timings) = timing::record(|| ());
+    assert!(timings.is_empty());
+}
+
+#[test]
+fn runs_are_summarized_by_phase() {
+    let ms = Duration::from_millis;
+    let runs = vec![
+        vec![("lex", ms(3)), ("parse", ms(1))],
+        vec![("lex", ms(1)), ("parse", ms(2))],
+        vec![("lex", ms(2)), ("parse", ms(9))],
+    ];
+    assert_eq!(
+        timing::summarize(&runs),
+        vec![
+            (
+                "lex",
+                Summary {
+                    min: ms(1),
+                    median: ms(2),
+                    max: ms(3),
+                },
+            ),
+            (
+                "parse",
+                Summary {
+                    min: ms(1),
+                    median: ms(2),
+                    max: ms(9),
+                },
+            ),
+        ]
+    );
+}